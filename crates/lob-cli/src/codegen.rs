@@ -2,7 +2,7 @@
 
 use crate::error::Result;
 use crate::input::{InputFormat, InputSource};
-use crate::output::OutputFormat;
+use crate::output::{OutputFormat, TableStyle};
 
 /// Generates Rust source code from a lob expression
 pub struct CodeGenerator {
@@ -10,6 +10,28 @@ pub struct CodeGenerator {
     input_source: InputSource,
     output_format: OutputFormat,
     enable_stats: bool,
+    select: Vec<String>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    input_limit: Option<usize>,
+    jobs: Option<usize>,
+    explain: bool,
+    table_style: TableStyle,
+    fields: Option<String>,
+    pre: Vec<String>,
+    distinct: bool,
+    json_path: Option<String>,
+    progress: bool,
+    summary: bool,
+    resilient: bool,
+    max_output: Option<usize>,
+    seed: Option<u64>,
+    null_values: Vec<String>,
+    strict: bool,
+    typed: bool,
+    encoding: Option<String>,
+    pretty: bool,
+    trailing_newline: bool,
 }
 
 impl CodeGenerator {
@@ -25,9 +47,163 @@ impl CodeGenerator {
             input_source,
             output_format,
             enable_stats,
+            select: Vec::new(),
+            head: None,
+            tail: None,
+            input_limit: None,
+            jobs: None,
+            explain: false,
+            table_style: TableStyle::default(),
+            fields: None,
+            pre: Vec::new(),
+            distinct: false,
+            json_path: None,
+            progress: false,
+            summary: false,
+            resilient: false,
+            max_output: None,
+            seed: None,
+            null_values: Vec::new(),
+            strict: false,
+            typed: false,
+            encoding: None,
+            pretty: false,
+            trailing_newline: true,
         }
     }
 
+    /// Project CSV/TSV rows down to the given columns before the expression runs
+    pub fn with_select(mut self, columns: Vec<String>) -> Self {
+        self.select = columns;
+        self
+    }
+
+    /// Keep only the first `n` items of a non-terminal expression
+    pub fn with_head(mut self, n: Option<usize>) -> Self {
+        self.head = n;
+        self
+    }
+
+    /// Keep only the last `n` items of a non-terminal expression
+    pub fn with_tail(mut self, n: Option<usize>) -> Self {
+        self.tail = n;
+        self
+    }
+
+    /// Cap the number of input records fed to the expression
+    pub fn with_input_limit(mut self, n: Option<usize>) -> Self {
+        self.input_limit = n;
+        self
+    }
+
+    /// Annotate the generated source with comments explaining each choice
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Border style used by table output
+    pub fn with_table_style(mut self, style: TableStyle) -> Self {
+        self.table_style = style;
+        self
+    }
+
+    /// Slice line input into fixed-width fields using a spec like `1-5,10-20`
+    pub fn with_fields(mut self, spec: Option<String>) -> Self {
+        self.fields = spec;
+        self
+    }
+
+    /// Statements emitted verbatim, in order, before the expression is evaluated
+    pub fn with_pre(mut self, statements: Vec<String>) -> Self {
+        self.pre = statements;
+        self
+    }
+
+    /// Drop duplicate input records before the expression runs
+    pub fn with_distinct(mut self, distinct: bool) -> Self {
+        self.distinct = distinct;
+        self
+    }
+
+    /// Replace each JSON record with the value at a dotted path, dropping misses
+    pub fn with_json_path(mut self, path: Option<String>) -> Self {
+        self.json_path = path;
+        self
+    }
+
+    /// Run a plain `map`/`filter` chain on this many threads
+    pub fn with_jobs(mut self, jobs: Option<usize>) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Print a running count of input records to stderr
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Print the number of emitted records and the elapsed time to stderr
+    pub fn with_summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Skip iterator items whose processing panics instead of aborting
+    pub fn with_resilient(mut self, resilient: bool) -> Self {
+        self.resilient = resilient;
+        self
+    }
+
+    /// Print at most this many records, noting how many were left out on stderr
+    pub fn with_max_output(mut self, max_output: Option<usize>) -> Self {
+        self.max_output = max_output;
+        self
+    }
+
+    /// Fix the seed used by randomized operations such as `sample`
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// CSV/TSV cell values to treat as missing
+    pub fn with_null_values(mut self, values: Vec<String>) -> Self {
+        self.null_values = values;
+        self
+    }
+
+    /// Stop at the first unreadable or malformed input record instead of skipping it
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Turn numeric and boolean CSV cells into JSON values
+    pub fn with_typed(mut self, typed: bool) -> Self {
+        self.typed = typed;
+        self
+    }
+
+    /// Decode line input from this encoding instead of UTF-8
+    pub fn with_encoding(mut self, encoding: Option<String>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Indent each JSON Lines record (JSON output is always indented)
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Whether the last debug/jsonl record ends with a newline
+    pub fn with_trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
     /// Generate complete Rust program from expression
     pub fn generate(&self) -> Result<String> {
         let mut code = String::new();
@@ -44,13 +220,19 @@ impl CodeGenerator {
         }
 
         // Add serde_json import if using JSON output (from lob_prelude re-export)
-        if matches!(
-            self.output_format,
-            OutputFormat::Json | OutputFormat::JsonLines
-        ) {
+        if self.typed
+            || matches!(
+                self.output_format,
+                OutputFormat::Json | OutputFormat::JsonLines
+            )
+        {
             code.push_str("use lob_prelude::serde_json;\n");
         }
 
+        if self.jobs.is_some() && self.is_parallelizable() {
+            code.push_str("use lob_prelude::rayon::prelude::*;\n");
+        }
+
         // Add tabled import if using Table output
         if matches!(self.output_format, OutputFormat::Table) {
             code.push_str("use lob_prelude::tabled::builder::Builder;\n");
@@ -58,7 +240,11 @@ impl CodeGenerator {
         }
 
         code.push('\n');
+        if let Some(seed) = self.seed {
+            code.push_str(&format!("const LOB_SEED: u64 = {};\n\n", seed));
+        }
         code.push_str("fn main() {\n");
+        self.generate_setup(&mut code);
 
         // Initialize stats tracking if enabled
         if self.enable_stats {
@@ -74,7 +260,9 @@ impl CodeGenerator {
 
         // Generate input based on format and source
         let expression = if uses_stdin {
+            self.explain_input(&mut code);
             self.generate_input(&mut code);
+            self.generate_input_adapters(&mut code);
             if self.enable_stats {
                 // Wrap iterator with stats tracking
                 code.push_str("    let stdin_data = {\n");
@@ -98,16 +286,29 @@ impl CodeGenerator {
                 code.push_str("        })\n");
                 code.push_str("    };\n");
             }
-            self.expression.replacen('_', "stdin_data", 1)
+            self.generate_parallel(&mut code)
+                .unwrap_or_else(|| self.expression.replacen('_', "stdin_data", 1))
         } else {
+            self.explain_line(
+                &mut code,
+                "Input: none, the expression does not start with `_` so stdin and files are not read",
+            );
             self.expression.clone()
         };
 
+        // User statements from --pre
+        for statement in &self.pre {
+            code.push_str(&format!("    {}\n", statement));
+        }
+
         // User expression
         code.push_str(&format!("    let result = {};\n", expression));
+        self.generate_resilient(&mut code);
+        self.generate_limit(&mut code);
+        self.explain_output(&mut code);
 
         // Generate output based on format
-        self.generate_output(&mut code);
+        self.generate_emit(&mut code);
 
         // Print final stats if enabled
         if self.enable_stats {
@@ -125,41 +326,462 @@ impl CodeGenerator {
         Ok(code)
     }
 
+    /// Emit a `// ...` comment line when explain mode is on
+    fn explain_line(&self, code: &mut String, comment: &str) {
+        if self.explain {
+            code.push_str(&format!("    // {}\n", comment));
+        }
+    }
+
+    /// Explain which input helper was chosen and why
+    fn explain_input(&self, code: &mut String) {
+        let (helper, reason) = match self.input_source.format {
+            InputFormat::Lines if self.fields.is_some() => (
+                "input_fields",
+                "fixed-width fields as Vec<String>, from --fields",
+            ),
+            InputFormat::Lines if self.encoding.is_some() => (
+                "input_encoded",
+                "plain text lines decoded to UTF-8, from --encoding",
+            ),
+            InputFormat::Lines => ("input", "plain text lines, no --parse-* flag was given"),
+            InputFormat::Csv => (
+                "input_csv",
+                "CSV rows as HashMap<String, String>, from --parse-csv",
+            ),
+            InputFormat::Tsv => (
+                "input_tsv",
+                "TSV rows as HashMap<String, String>, from --parse-tsv",
+            ),
+            InputFormat::JsonLines => (
+                "input_json",
+                "one serde_json::Value per line, from --parse-json",
+            ),
+            InputFormat::JsonArray => (
+                "input_json_array",
+                "one serde_json::Value per array element, from --parse-json-array",
+            ),
+            InputFormat::Logs => (
+                "input_logs",
+                "access log lines as HashMap<String, String>, from --parse-logs",
+            ),
+        };
+        let (helper, source) = if self.input_source.is_stdin() {
+            (
+                helper.to_string(),
+                "stdin, because no FILE arguments were given",
+            )
+        } else if self.encoding.is_some() {
+            (
+                helper.to_string(),
+                "the FILE arguments passed to the binary",
+            )
+        } else {
+            (
+                format!("{}_from_files", helper),
+                "the FILE arguments passed to the binary",
+            )
+        };
+        let helper = if self.has_strict_input() {
+            format!("{}_strict", helper)
+        } else {
+            helper
+        };
+        self.explain_line(code, &format!("Input: {}() yields {}", helper, reason));
+        self.explain_line(code, &format!("Source: {}", source));
+        self.explain_line(code, "`_` in the expression is replaced by `stdin_data`");
+    }
+
+    /// Explain terminal detection and the output path taken
+    fn explain_output(&self, code: &mut String) {
+        let is_iter = !self.has_terminal_operation();
+        if is_iter {
+            self.explain_line(
+                code,
+                "Terminal: none detected, so `result` is an iterator printed item by item",
+            );
+        } else {
+            self.explain_line(
+                code,
+                "Terminal: detected, so `result` is a single value printed once",
+            );
+        }
+
+        let path = match (self.output_format, is_iter) {
+            (OutputFormat::Debug, true) => "debug, each item printed with {:?}",
+            (OutputFormat::Debug, false) => "debug, the value printed with {:?}",
+            (OutputFormat::Json, true) => "json, items streamed into a pretty-printed array",
+            (OutputFormat::Json, false) => "json, the value serialized with serde_json",
+            (OutputFormat::JsonLines, true) => "jsonl, one JSON document per item",
+            (OutputFormat::JsonLines, false) => "jsonl, the value as a single JSON line",
+            (OutputFormat::Csv, true) => "csv, items collected and written as rows",
+            (OutputFormat::Csv, false) => "csv, the value written as a single row",
+            (OutputFormat::Table, true) => "table, items collected and rendered with tabled",
+            (OutputFormat::Table, false) => "table, the value rendered as a one-row table",
+            (OutputFormat::Toml, true) => "toml, items collected into an [[items]] array of tables",
+            (OutputFormat::Toml, false) => "toml, the value serialized as a TOML table",
+            (OutputFormat::Yaml, true) => "yaml, items collected into a YAML sequence",
+            (OutputFormat::Yaml, false) => "yaml, the value serialized as a single YAML document",
+        };
+        self.explain_line(code, &format!("Output: {}", path));
+    }
+
+    /// Whether `--strict` selects a `_strict` input helper for this input
+    ///
+    /// Fixed-width, encoded, JSON array and log input have no strict variant.
+    fn has_strict_input(&self) -> bool {
+        self.strict
+            && match self.input_source.format {
+                InputFormat::Lines => self.fields.is_none() && self.encoding.is_none(),
+                InputFormat::Csv | InputFormat::Tsv | InputFormat::JsonLines => true,
+                InputFormat::JsonArray | InputFormat::Logs => false,
+            }
+    }
+
     /// Generate input code based on input source and format
     fn generate_input(&self, code: &mut String) {
+        let strict = if self.has_strict_input() {
+            "_strict"
+        } else {
+            ""
+        };
         match self.input_source.format {
+            InputFormat::Lines if self.fields.is_some() => {
+                let spec = format!("{:?}", self.fields.as_deref().unwrap_or_default());
+                if self.input_source.is_stdin() {
+                    code.push_str(&format!("    let stdin_data = input_fields({});\n", spec));
+                } else {
+                    code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
+                    code.push_str(&format!(
+                        "    let stdin_data = input_fields_from_files(&files, {});\n",
+                        spec
+                    ));
+                }
+            }
+            InputFormat::Lines if self.encoding.is_some() => {
+                let encoding = format!("{:?}", self.encoding.as_deref().unwrap_or_default());
+                if self.input_source.is_stdin() {
+                    code.push_str(&format!(
+                        "    let stdin_data = input_encoded(&[], {});\n",
+                        encoding
+                    ));
+                } else {
+                    code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
+                    code.push_str(&format!(
+                        "    let stdin_data = input_encoded(&files, {});\n",
+                        encoding
+                    ));
+                }
+            }
             InputFormat::Lines => {
                 if self.input_source.is_stdin() {
-                    code.push_str("    let stdin_data = input();\n");
+                    code.push_str(&format!("    let stdin_data = input{}();\n", strict));
                 } else {
                     code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
-                    code.push_str("    let stdin_data = input_from_files(&files);\n");
+                    code.push_str(&format!(
+                        "    let stdin_data = input_from_files{}(&files);\n",
+                        strict
+                    ));
                 }
             }
             InputFormat::Csv => {
                 if self.input_source.is_stdin() {
-                    code.push_str("    let stdin_data = input_csv();\n");
+                    code.push_str(&format!("    let stdin_data = input_csv{}();\n", strict));
                 } else {
                     code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
-                    code.push_str("    let stdin_data = input_csv_from_files(&files);\n");
+                    code.push_str(&format!(
+                        "    let stdin_data = input_csv_from_files{}(&files);\n",
+                        strict
+                    ));
                 }
             }
             InputFormat::Tsv => {
                 if self.input_source.is_stdin() {
-                    code.push_str("    let stdin_data = input_tsv();\n");
+                    code.push_str(&format!("    let stdin_data = input_tsv{}();\n", strict));
                 } else {
                     code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
-                    code.push_str("    let stdin_data = input_tsv_from_files(&files);\n");
+                    code.push_str(&format!(
+                        "    let stdin_data = input_tsv_from_files{}(&files);\n",
+                        strict
+                    ));
                 }
             }
             InputFormat::JsonLines => {
                 if self.input_source.is_stdin() {
-                    code.push_str("    let stdin_data = input_json();\n");
+                    code.push_str(&format!("    let stdin_data = input_json{}();\n", strict));
                 } else {
                     code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
-                    code.push_str("    let stdin_data = input_json_from_files(&files);\n");
+                    code.push_str(&format!(
+                        "    let stdin_data = input_json_from_files{}(&files);\n",
+                        strict
+                    ));
                 }
             }
+            InputFormat::JsonArray => {
+                if self.input_source.is_stdin() {
+                    code.push_str("    let stdin_data = input_json_array();\n");
+                } else {
+                    code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
+                    code.push_str("    let stdin_data = input_json_array_from_files(&files);\n");
+                }
+            }
+            InputFormat::Logs => {
+                if self.input_source.is_stdin() {
+                    code.push_str("    let stdin_data = input_logs();\n");
+                } else {
+                    code.push_str("    let files: Vec<_> = std::env::args().skip(1).map(|p| std::path::PathBuf::from(p)).collect();\n");
+                    code.push_str("    let stdin_data = input_logs_from_files(&files);\n");
+                }
+            }
+        }
+    }
+
+    /// Generate prelude configuration calls that must run before any input is read
+    fn generate_setup(&self, code: &mut String) {
+        if self.summary {
+            code.push_str("    let lob_start = std::time::Instant::now();\n");
+        }
+        if self.seed.is_some() {
+            self.explain_line(code, "--seed: randomized operations use LOB_SEED");
+            code.push_str("    set_seed(LOB_SEED);\n");
+        }
+        if !self.null_values.is_empty()
+            && matches!(
+                self.input_source.format,
+                InputFormat::Csv | InputFormat::Tsv
+            )
+        {
+            self.explain_line(
+                code,
+                "--null-value: matching cells are left out of each row",
+            );
+            let values: Vec<String> = self
+                .null_values
+                .iter()
+                .map(|v| format!("{:?}", v))
+                .collect();
+            code.push_str(&format!("    set_null_values(&[{}]);\n", values.join(", ")));
+        }
+    }
+
+    /// Generate adapters applied to the raw input before the user expression
+    fn generate_input_adapters(&self, code: &mut String) {
+        if self.progress {
+            self.explain_line(
+                code,
+                "--progress: input records are counted and throughput goes to stderr",
+            );
+            code.push_str("    let stdin_data = progress(stdin_data);\n");
+        }
+
+        if let Some(n) = self.input_limit {
+            self.explain_line(
+                code,
+                "--input-limit: only the first N input records are read",
+            );
+            code.push_str(&format!("    let stdin_data = stdin_data.take({});\n", n));
+        }
+
+        if !self.select.is_empty()
+            && matches!(
+                self.input_source.format,
+                InputFormat::Csv | InputFormat::Tsv | InputFormat::Logs
+            )
+        {
+            self.explain_line(code, "--select: each row keeps only the named columns");
+            let columns: Vec<String> = self.select.iter().map(|c| format!("{:?}", c)).collect();
+            code.push_str(&format!(
+                "    let stdin_data = stdin_data.map(|row| row.into_iter().filter(|(k, _)| [{}].contains(&k.as_str())).collect::<HashMap<_, _>>());\n",
+                columns.join(", ")
+            ));
+        }
+
+        if self.typed && self.input_source.format == InputFormat::Csv {
+            self.explain_line(
+                code,
+                "--typed: rows become HashMap<String, serde_json::Value> with numbers and booleans",
+            );
+            code.push_str("    let stdin_data = typed_rows(stdin_data);\n");
+        }
+
+        if let Some(path) = &self.json_path {
+            if matches!(
+                self.input_source.format,
+                InputFormat::JsonLines | InputFormat::JsonArray
+            ) {
+                self.explain_line(
+                    code,
+                    "--parse-json-path: each record becomes the value at the path",
+                );
+                code.push_str(&format!(
+                    "    let stdin_data = stdin_data.filter_map(|v| json_path(&v, {:?}).cloned());\n",
+                    path
+                ));
+            }
+        }
+
+        if self.distinct {
+            self.explain_line(
+                code,
+                "--distinct: duplicate records are dropped with unique()",
+            );
+            code.push_str("    let stdin_data = stdin_data.unique();\n");
+        }
+
+        if self.resilient && !self.has_terminal_operation() {
+            self.explain_line(
+                code,
+                "--resilient: input records are counted so warnings can name the one that panicked",
+            );
+            code.push_str("    let stdin_data = count_records(stdin_data);\n");
+        }
+    }
+
+    /// Generate the `--resilient` wrapper that skips items whose processing panics
+    fn generate_resilient(&self, code: &mut String) {
+        if !self.resilient || self.has_terminal_operation() {
+            return;
+        }
+        self.explain_line(
+            code,
+            "--resilient: a panic while producing an item skips that item with a warning",
+        );
+        code.push_str("    let result = resilient(result);\n");
+    }
+
+    /// Generate `--head`/`--tail` truncation of the expression result
+    fn generate_limit(&self, code: &mut String) {
+        if self.has_terminal_operation() {
+            return;
+        }
+        if self.head.is_some() || self.tail.is_some() {
+            self.explain_line(code, "--head/--tail: truncate the iterator result");
+        }
+        if let Some(n) = self.head {
+            code.push_str(&format!("    let result = lob(result).take({});\n", n));
+        }
+        if let Some(n) = self.tail {
+            code.push_str(&format!(
+                "    let result = lob(lob(result).take_last({}));\n",
+                n
+            ));
+        }
+    }
+
+    /// Print one record per item; `args` are the format arguments for `item`
+    fn generate_record_loop(&self, code: &mut String, args: &str) {
+        if self.trailing_newline {
+            code.push_str("    for item in result {\n");
+            code.push_str(&format!("        println!({});\n", args));
+        } else {
+            // Newlines go between records, so the last one is left unterminated
+            code.push_str("    for (i, item) in result.into_iter().enumerate() {\n");
+            code.push_str("        if i > 0 {\n");
+            code.push_str("            println!();\n");
+            code.push_str("        }\n");
+            code.push_str(&format!("        print!({});\n", args));
+        }
+        code.push_str("    }\n");
+    }
+
+    /// Print a single record; `args` are the format arguments for `result`
+    fn generate_record(&self, code: &mut String, args: &str) {
+        let print = if self.trailing_newline {
+            "println"
+        } else {
+            "print"
+        };
+        code.push_str(&format!("    {}!({});\n", print, args));
+    }
+
+    /// Generate the `--jobs` setup and return the parallel form of the expression
+    ///
+    /// Returns `None` when `--jobs` is off or the expression is not a plain
+    /// `map`/`filter`/`filter_map` chain, in which case it runs sequentially.
+    fn generate_parallel(&self, code: &mut String) -> Option<String> {
+        let jobs = self.jobs?;
+        let chain = self.expression.trim().strip_prefix('_')?;
+        if !self.is_parallelizable() {
+            return None;
+        }
+
+        self.explain_line(
+            code,
+            &format!(
+                "--jobs: input is collected, then mapped on {} threads in input order",
+                jobs
+            ),
+        );
+        code.push_str("    let stdin_data: Vec<_> = stdin_data.collect();\n");
+        code.push_str(&format!(
+            "    let pool = lob_prelude::rayon::ThreadPoolBuilder::new().num_threads({}).build().unwrap();\n",
+            jobs
+        ));
+        Some(format!(
+            "lob(pool.install(|| stdin_data.into_par_iter(){}.collect::<Vec<_>>()))",
+            chain.trim()
+        ))
+    }
+
+    /// Whether the expression is `_` followed only by `map`/`filter`/`filter_map` calls
+    ///
+    /// Such chains keep their meaning when run on a rayon parallel iterator,
+    /// which preserves input order when collected.
+    pub fn is_parallelizable(&self) -> bool {
+        let Some(chain) = self.expression.trim().strip_prefix('_') else {
+            return false;
+        };
+        // Output is generated from terminal detection, so a closure that
+        // looks like a terminal (`.sum::<u64>()`) keeps the sequential path
+        !self.has_terminal_operation()
+            && split_method_chain(chain).is_some_and(|calls| {
+                !calls.is_empty()
+                    && calls
+                        .iter()
+                        .all(|name| matches!(*name, "map" | "filter" | "filter_map"))
+            })
+    }
+
+    /// Generate output, capped by `--max-output` and counted for `--summary`
+    fn generate_emit(&self, code: &mut String) {
+        let is_iter = !self.has_terminal_operation();
+        let max_output = self.max_output.filter(|_| is_iter);
+
+        if let Some(n) = max_output {
+            self.explain_line(
+                code,
+                "--max-output: printing stops at the cap; one more record is read to tell if any were left",
+            );
+            code.push_str("    let mut lob_rest = result.into_iter();\n");
+            code.push_str(&format!(
+                "    let result = lob(lob_rest.by_ref().take({}));\n",
+                n
+            ));
+        }
+        if self.summary {
+            self.explain_line(
+                code,
+                "--summary: emitted records are counted and reported on stderr",
+            );
+        }
+        if self.summary && is_iter {
+            code.push_str("    let lob_emitted = std::cell::Cell::new(0usize);\n");
+            code.push_str(
+                "    let result = lob(result.into_iter().inspect(|_| lob_emitted.set(lob_emitted.get() + 1)));\n",
+            );
+        }
+
+        self.generate_output(code);
+
+        if self.summary {
+            let emitted = if is_iter { "lob_emitted.get()" } else { "1" };
+            code.push_str(&format!("    print_summary({}, lob_start);\n", emitted));
+        }
+        if max_output.is_some() {
+            // Peek rather than count: the rest of the input may never end
+            code.push_str("    if lob_rest.next().is_some() {\n");
+            code.push_str("        eprintln!(\"... (more rows)\");\n");
+            code.push_str("    }\n");
         }
     }
 
@@ -170,19 +792,14 @@ impl CodeGenerator {
         match self.output_format {
             OutputFormat::Debug => {
                 if is_iter {
-                    code.push_str("    for item in result {\n");
-                    code.push_str("        println!(\"{:?}\", item);\n");
-                    code.push_str("    }\n");
+                    self.generate_record_loop(code, "\"{:?}\", item");
                 } else {
-                    code.push_str("    println!(\"{:?}\", result);\n");
+                    self.generate_record(code, "\"{:?}\", result");
                 }
             }
             OutputFormat::Json => {
                 if is_iter {
-                    code.push_str("    let items: Vec<_> = result.collect();\n");
-                    code.push_str(
-                        "    println!(\"{}\", serde_json::to_string_pretty(&items).unwrap());\n",
-                    );
+                    code.push_str("    output_json_stream(result);\n");
                 } else {
                     code.push_str(
                         "    println!(\"{}\", serde_json::to_string(&result).unwrap());\n",
@@ -190,15 +807,20 @@ impl CodeGenerator {
                 }
             }
             OutputFormat::JsonLines => {
+                let to_string = if self.pretty {
+                    "to_string_pretty"
+                } else {
+                    "to_string"
+                };
                 if is_iter {
-                    code.push_str("    for item in result {\n");
-                    code.push_str(
-                        "        println!(\"{}\", serde_json::to_string(&item).unwrap());\n",
+                    self.generate_record_loop(
+                        code,
+                        &format!("\"{{}}\", serde_json::{}(&item).unwrap()", to_string),
                     );
-                    code.push_str("    }\n");
                 } else {
-                    code.push_str(
-                        "    println!(\"{}\", serde_json::to_string(&result).unwrap());\n",
+                    self.generate_record(
+                        code,
+                        &format!("\"{{}}\", serde_json::{}(&result).unwrap()", to_string),
                     );
                 }
             }
@@ -210,37 +832,53 @@ impl CodeGenerator {
                     code.push_str("    output_csv(&[result]);\n");
                 }
             }
+            OutputFormat::Toml => {
+                if is_iter {
+                    code.push_str("    let items: Vec<_> = result.collect();\n");
+                    code.push_str("    output_toml(&HashMap::from([(\"items\", items)]));\n");
+                } else {
+                    code.push_str("    output_toml(&result);\n");
+                }
+            }
+            OutputFormat::Yaml => {
+                if is_iter {
+                    code.push_str("    let items: Vec<_> = result.collect();\n");
+                    code.push_str("    output_yaml(&items);\n");
+                } else {
+                    code.push_str("    output_yaml(&result);\n");
+                }
+            }
             OutputFormat::Table => {
                 if is_iter {
                     code.push_str("    let items: Vec<_> = result.collect();\n");
                     code.push_str("    if !items.is_empty() {\n");
                     code.push_str("        let mut builder = Builder::default();\n");
-                    code.push_str("        // Extract headers from first item\n");
-                    code.push_str("        let mut headers: Vec<_> = items[0].keys().collect();\n");
-                    code.push_str("        headers.sort();\n");
+                    code.push_str("        // Headers from every item, in source column order\n");
                     code.push_str(
-                        "        builder.push_record(headers.iter().map(|k| k.as_str()));\n",
+                        "        let headers = header_keys(items.iter().map(|item| item.keys()));\n",
                     );
+                    code.push_str("        builder.push_record(headers.iter().copied());\n");
                     code.push_str("        // Add data rows\n");
                     code.push_str("        for item in &items {\n");
                     code.push_str("            let row: Vec<_> = headers.iter().map(|k| item.get(*k).map(|v| v.as_str()).unwrap_or(\"\")).collect();\n");
                     code.push_str("            builder.push_record(row);\n");
                     code.push_str("        }\n");
-                    code.push_str(
-                        "        let table = builder.build().with(Style::rounded()).to_string();\n",
-                    );
+                    code.push_str(&format!(
+                        "        let table = builder.build().with({}).to_string();\n",
+                        self.table_style.constructor()
+                    ));
                     code.push_str("        println!(\"{}\", table);\n");
                     code.push_str("    }\n");
                 } else {
                     code.push_str("    let mut builder = Builder::default();\n");
-                    code.push_str("    let mut headers: Vec<_> = result.keys().collect();\n");
-                    code.push_str("    headers.sort();\n");
-                    code.push_str("    builder.push_record(headers.iter().map(|k| k.as_str()));\n");
+                    code.push_str("    let headers = ordered_keys(result.keys());\n");
+                    code.push_str("    builder.push_record(headers.iter().copied());\n");
                     code.push_str("    let row: Vec<_> = headers.iter().map(|k| result.get(*k).map(|v| v.as_str()).unwrap_or(\"\")).collect();\n");
                     code.push_str("    builder.push_record(row);\n");
-                    code.push_str(
-                        "    let table = builder.build().with(Style::rounded()).to_string();\n",
-                    );
+                    code.push_str(&format!(
+                        "    let table = builder.build().with({}).to_string();\n",
+                        self.table_style.constructor()
+                    ));
                     code.push_str("    println!(\"{}\", table);\n");
                 }
             }
@@ -248,24 +886,387 @@ impl CodeGenerator {
     }
 
     /// Check if expression has a terminal operation
-    fn has_terminal_operation(&self) -> bool {
+    pub fn has_terminal_operation(&self) -> bool {
         let terminals = [
             ".collect(",
             ".count()",
+            ".count_distinct()",
             ".sum(",
             ".sum::",
             ".min()",
             ".max()",
+            ".min_max()",
+            ".argmax()",
+            ".argmin()",
+            ".percentile(",
+            ".histogram(",
+            ".mode()",
+            ".try_map(",
+            ".try_collect(",
+            ".try_collect::",
+            ".split_results()",
             ".reduce(",
             ".fold(",
             ".fold_left(",
+            ".group_by_agg(",
+            ".partition_by(",
+            ".max_by_group(",
+            ".nth_largest(",
+            ".nth_smallest(",
             ".first()",
+            ".find_map(",
+            ".positions(",
+            ".sample(",
+            ".sample_seeded(",
+            ".sample_weighted(",
+            ".first_where(",
+            ".last_where(",
             ".last()",
+            ".take_last(",
+            ".take_reporting(",
             ".to_list()",
+            ".collect_strings()",
+            ".collect_set()",
             ".any(",
             ".all(",
         ];
 
+        // `.is_empty()` is common inside closures (`filter(|l| !l.is_empty())`),
+        // so it only counts as a terminal at the end of the expression
         terminals.iter().any(|t| self.expression.contains(t))
+            || self.expression.trim_end().ends_with(".is_empty()")
+    }
+}
+
+/// Split `.a(..).b(..)` into its method names, or `None` if it is anything else
+///
+/// Parentheses are matched while skipping over string and char literals, so
+/// closures such as `|l| l.replace("(", "")` are handled.
+fn split_method_chain(chain: &str) -> Option<Vec<&str>> {
+    let bytes = chain.as_bytes();
+    let mut names = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if bytes[i] != b'.' {
+            return None;
+        }
+        let start = i + 1;
+        let open = start + chain[start..].find('(')?;
+        let name = chain[start..open].trim();
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+            return None;
+        }
+        names.push(name);
+        i = skip_balanced(bytes, open)?;
+    }
+    Some(names)
+}
+
+/// Return the index just past the group that opens at `bytes[open]`
+fn skip_balanced(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            // Char literals such as '(' or '\''; lifetimes are left alone
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b'\'' if bytes.get(i + 1) == Some(&b'\\') => {
+                i += 2;
+                while i < bytes.len() && bytes[i] != b'\'' {
+                    i += 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generator(expression: &str) -> CodeGenerator {
+        CodeGenerator::new(
+            expression.to_string(),
+            InputSource::new(Vec::new(), InputFormat::Csv),
+            OutputFormat::Json,
+            false,
+        )
+    }
+
+    #[test]
+    fn explain_adds_comments() {
+        let source = generator("_.count()")
+            .with_explain(true)
+            .generate()
+            .unwrap();
+        assert!(source.contains("// Input: input_csv() yields CSV rows"));
+        assert!(source.contains("// Source: stdin"));
+        assert!(source.contains("// Terminal: detected"));
+        assert!(source.contains("// Output: json, the value serialized"));
+    }
+
+    #[test]
+    fn explain_iterator_output_path() {
+        let source = generator("_.take(2)")
+            .with_explain(true)
+            .generate()
+            .unwrap();
+        assert!(source.contains("// Terminal: none detected"));
+        assert!(source.contains("// Output: json, items streamed"));
+    }
+
+    #[test]
+    fn pre_statements_in_order_before_result() {
+        let source = generator("_.take(2)")
+            .with_pre(vec!["let a = 1;".to_string(), "let b = a + 1;".to_string()])
+            .generate()
+            .unwrap();
+        let a = source.find("    let a = 1;\n").unwrap();
+        let b = source.find("    let b = a + 1;\n").unwrap();
+        let result = source.find("let result =").unwrap();
+        assert!(a < b && b < result);
+        assert!(source.find("let stdin_data").unwrap() < a);
+    }
+
+    #[test]
+    fn no_comments_without_explain() {
+        let source = generator("_.count()").generate().unwrap();
+        assert!(!source.contains("// "));
+    }
+
+    #[test]
+    fn progress_wraps_input_first() {
+        let source = generator("_.count()")
+            .with_progress(true)
+            .with_input_limit(Some(10))
+            .generate()
+            .unwrap();
+        let wrap = source
+            .find("let stdin_data = progress(stdin_data);")
+            .unwrap();
+        assert!(wrap < source.find("stdin_data.take(10)").unwrap());
+
+        let source = generator("_.count()").generate().unwrap();
+        assert!(!source.contains("progress("));
+    }
+
+    #[test]
+    fn seed_emits_const_and_sets_it() {
+        let source = generator("_.sample(3)")
+            .with_seed(Some(42))
+            .generate()
+            .unwrap();
+        let constant = source.find("const LOB_SEED: u64 = 42;").unwrap();
+        let main = source.find("fn main() {").unwrap();
+        let set = source.find("    set_seed(LOB_SEED);").unwrap();
+        assert!(constant < main && main < set);
+        assert!(set < source.find("let stdin_data").unwrap());
+
+        let source = generator("_.sample(3)").generate().unwrap();
+        assert!(!source.contains("LOB_SEED"));
+    }
+
+    #[test]
+    fn null_values_set_before_input() {
+        let source = generator("_.count()")
+            .with_null_values(vec!["NA".to_string(), "\\N".to_string()])
+            .generate()
+            .unwrap();
+        let set = source
+            .find(r#"    set_null_values(&["NA", "\\N"]);"#)
+            .unwrap();
+        assert!(set < source.find("let stdin_data").unwrap());
+    }
+
+    #[test]
+    fn typed_converts_csv_rows() {
+        let source = CodeGenerator::new(
+            "_.map(|r| r[\"age\"].clone())".to_string(),
+            InputSource::new(Vec::new(), InputFormat::Csv),
+            OutputFormat::Debug,
+            false,
+        )
+        .with_typed(true)
+        .generate()
+        .unwrap();
+        assert!(source.contains("use lob_prelude::serde_json;\n"));
+        let input = source.find("    let stdin_data = input_csv();\n").unwrap();
+        let typed = source
+            .find("    let stdin_data = typed_rows(stdin_data);\n")
+            .unwrap();
+        assert!(input < typed);
+    }
+
+    #[test]
+    fn resilient_wraps_iterator_results() {
+        let source = generator("_.map(|r| r.len())")
+            .with_resilient(true)
+            .generate()
+            .unwrap();
+        let counted = source
+            .find("    let stdin_data = count_records(stdin_data);\n")
+            .unwrap();
+        let wrapped = source
+            .find("    let result = resilient(result);\n")
+            .unwrap();
+        assert!(counted < wrapped);
+
+        let source = generator("_.count()")
+            .with_resilient(true)
+            .generate()
+            .unwrap();
+        assert!(!source.contains("resilient(result)"));
+        assert!(!source.contains("count_records"));
+    }
+
+    #[test]
+    fn max_output_caps_iterators_only() {
+        let source = generator("_").with_max_output(Some(3)).generate().unwrap();
+        assert!(source.contains("    let result = lob(lob_rest.by_ref().take(3));\n"));
+        assert!(source.contains("    if lob_rest.next().is_some() {\n"));
+        assert!(!source.contains("count()"));
+
+        let source = generator("_.count()")
+            .with_max_output(Some(3))
+            .generate()
+            .unwrap();
+        assert!(!source.contains("lob_rest"));
+    }
+
+    #[test]
+    fn parallelizable_chains() {
+        let parallel = |expression: &str| generator(expression).is_parallelizable();
+        assert!(parallel("_.map(|r| r.len())"));
+        assert!(parallel(
+            "_.filter(|l| l.contains(\")\")).map(|l| l.replace('(', \"\"))"
+        ));
+        assert!(parallel(
+            " _ .filter_map(|l| l.parse::<i64>().ok())\n.map(|n| n * 2) "
+        ));
+        assert!(!parallel("_"));
+        assert!(!parallel("_.map(|x| x).count()"));
+        assert!(!parallel("_.take(5)"));
+        assert!(!parallel("_.map(|x| x) + 1"));
+        assert!(!parallel("range(0, 5).map(|x| x)"));
+        assert!(!parallel("_.map(|l| l.split(',').count())"));
+    }
+
+    #[test]
+    fn jobs_generates_rayon_pipeline() {
+        let source = generator("_.map(|r| r.len())")
+            .with_jobs(Some(4))
+            .generate()
+            .unwrap();
+        assert!(source.contains("use lob_prelude::rayon::prelude::*;"));
+        assert!(source.contains("num_threads(4)"));
+        assert!(source.contains(
+            "let result = lob(pool.install(|| stdin_data.into_par_iter().map(|r| r.len()).collect::<Vec<_>>()));"
+        ));
+
+        let source = generator("_.take(2)")
+            .with_jobs(Some(4))
+            .generate()
+            .unwrap();
+        assert!(!source.contains("rayon"));
+    }
+
+    #[test]
+    fn encoding_uses_input_encoded() {
+        let lines = |files: Vec<std::path::PathBuf>| {
+            CodeGenerator::new(
+                "_.count()".to_string(),
+                InputSource::new(files, InputFormat::Lines),
+                OutputFormat::Json,
+                false,
+            )
+            .with_encoding(Some("windows-1252".to_string()))
+        };
+        let source = lines(Vec::new()).generate().unwrap();
+        assert!(source.contains("    let stdin_data = input_encoded(&[], \"windows-1252\");\n"));
+
+        let source = lines(vec!["a.log".into()]).generate().unwrap();
+        assert!(source.contains("    let stdin_data = input_encoded(&files, \"windows-1252\");\n"));
+    }
+
+    #[test]
+    fn strict_uses_strict_input_helpers() {
+        let source = generator("_.count()")
+            .with_strict(true)
+            .with_explain(true)
+            .generate()
+            .unwrap();
+        assert!(source.contains("    let stdin_data = input_csv_strict();\n"));
+        assert!(source.contains("// Input: input_csv_strict() yields CSV rows"));
+
+        let source = CodeGenerator::new(
+            "_.count()".to_string(),
+            InputSource::new(vec!["a.json".into()], InputFormat::JsonLines),
+            OutputFormat::Json,
+            false,
+        )
+        .with_strict(true)
+        .generate()
+        .unwrap();
+        assert!(source.contains("    let stdin_data = input_json_from_files_strict(&files);\n"));
+    }
+
+    #[test]
+    fn strict_explain_matches_inputs_without_strict_helper() {
+        let source = CodeGenerator::new(
+            "_.count()".to_string(),
+            InputSource::new(Vec::new(), InputFormat::Lines),
+            OutputFormat::Debug,
+            false,
+        )
+        .with_encoding(Some("windows-1252".to_string()))
+        .with_strict(true)
+        .with_explain(true)
+        .generate()
+        .unwrap();
+        assert!(source.contains("    let stdin_data = input_encoded(&[], \"windows-1252\");\n"));
+        assert!(source.contains("// Input: input_encoded() yields"));
+        assert!(!source.contains("_strict"));
+    }
+
+    #[test]
+    fn no_trailing_newline_prints_separators_between_records() {
+        let jsonl = |expression: &str| {
+            CodeGenerator::new(
+                expression.to_string(),
+                InputSource::new(Vec::new(), InputFormat::Lines),
+                OutputFormat::JsonLines,
+                false,
+            )
+            .with_trailing_newline(false)
+        };
+        let source = jsonl("_.take(2)").generate().unwrap();
+        assert!(source.contains("for (i, item) in result.into_iter().enumerate() {"));
+        assert!(source.contains("        print!(\"{}\", serde_json::to_string(&item).unwrap());"));
+
+        let source = jsonl("_.count()").generate().unwrap();
+        assert!(source.contains("    print!(\"{}\", serde_json::to_string(&result).unwrap());"));
     }
 }
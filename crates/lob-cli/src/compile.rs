@@ -1,7 +1,7 @@
 //! Compilation of generated Rust code
 
 use crate::cache::Cache;
-use crate::error::{LobError, Result};
+use crate::error::{ErrorFormat, LobError, Result};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -21,11 +21,14 @@ struct RlibPaths {
 }
 
 /// Compiler for lob expressions
+#[derive(Clone)]
 pub struct Compiler {
     /// Path to rustc executable
     rustc_path: PathBuf,
     /// Path to sysroot (for embedded toolchain)
     sysroot: Option<PathBuf>,
+    /// How compilation errors are rendered
+    error_format: ErrorFormat,
 }
 
 /// Find a file matching `{prefix}*.rlib` in a directory
@@ -172,6 +175,7 @@ impl Compiler {
         Ok(Self {
             rustc_path: PathBuf::from("rustc"),
             sysroot: None,
+            error_format: ErrorFormat::Human,
         })
     }
 
@@ -180,9 +184,16 @@ impl Compiler {
         Self {
             rustc_path,
             sysroot,
+            error_format: ErrorFormat::Human,
         }
     }
 
+    /// Set how compilation errors are rendered
+    pub fn with_error_format(mut self, error_format: ErrorFormat) -> Self {
+        self.error_format = error_format;
+        self
+    }
+
     /// Compile source code to binary
     pub fn compile(
         &self,
@@ -234,7 +245,8 @@ impl Compiler {
         if !output.status.success() {
             let _ = std::fs::remove_dir_all(&temp_dir);
             let stderr = String::from_utf8_lossy(&output.stderr);
-            let formatted = LobError::format_compilation_error(&stderr, user_expr);
+            let formatted =
+                LobError::format_compilation_error(&stderr, user_expr, self.error_format);
             return Err(LobError::Compilation(formatted));
         }
 
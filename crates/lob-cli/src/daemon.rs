@@ -0,0 +1,249 @@
+//! Background compile server that keeps the compiler warm
+//!
+//! The daemon listens on `daemon.sock` in the cache directory. Each request
+//! is one JSON line carrying generated source; each response is one JSON line
+//! with either the compiled binary path or the formatted compile error.
+//! Clients opt in by setting `LOB_DAEMON=1`.
+
+use crate::cache::Cache;
+use crate::error::ErrorFormat;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Environment variable that routes compiles through a running daemon
+pub const ENV_VAR: &str = "LOB_DAEMON";
+
+/// A compile request sent to the daemon
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Request {
+    /// Generated program source
+    pub source: String,
+    /// The user's expression, used to format compile errors
+    pub expression: Option<String>,
+    /// How to render a compile error
+    #[serde(default)]
+    pub error_format: ErrorFormat,
+}
+
+/// The daemon's reply to a [`Request`]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    /// The program compiled (or was already cached)
+    Compiled {
+        /// Path to the compiled binary
+        binary_path: PathBuf,
+        /// Whether the binary was found in cache
+        cache_hit: bool,
+    },
+    /// Compilation failed
+    Failed {
+        /// Error message, ready to print
+        error: String,
+    },
+}
+
+/// Path of the daemon socket inside the cache directory
+pub fn socket_path(cache: &Cache) -> PathBuf {
+    cache.cache_dir().join("daemon.sock")
+}
+
+#[cfg(not(unix))]
+pub use fallback::{compile, serve};
+#[cfg(unix)]
+pub use unix::{compile, serve};
+
+#[cfg(unix)]
+mod unix {
+    use super::{socket_path, Request, Response, ENV_VAR};
+    use crate::cache::Cache;
+    use crate::compile::{CompileResult, Compiler};
+    use crate::error::{ErrorFormat, LobError, Result};
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::Mutex;
+
+    /// Serve compile requests until the process is killed
+    ///
+    /// Each connection gets its own thread, so a client that connects and
+    /// never sends a request does not stall the others. Compiles still run
+    /// one at a time so that two clients asking for the same expression
+    /// never race to write the same cached binary.
+    pub fn serve(verbose: bool) -> Result<()> {
+        let cache = Cache::new()?;
+        let compiler = crate::initialize_compiler(verbose)?;
+        let path = socket_path(&cache);
+
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                return Err(LobError::InvalidExpression(format!(
+                    "A daemon is already listening on {}",
+                    path.display()
+                )));
+            }
+            // Left behind by a daemon that did not shut down cleanly
+            std::fs::remove_file(&path)?;
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        eprintln!("lob daemon listening on {}", path.display());
+
+        let compile_lock = Mutex::new(());
+        std::thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        continue;
+                    }
+                };
+                let (compiler, cache, compile_lock) = (&compiler, &cache, &compile_lock);
+                scope.spawn(move || {
+                    if let Err(e) = handle(&stream, compiler, cache, compile_lock, verbose) {
+                        eprintln!("Error: {}", e);
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+
+    fn handle(
+        stream: &UnixStream,
+        compiler: &Compiler,
+        cache: &Cache,
+        compile_lock: &Mutex<()>,
+        verbose: bool,
+    ) -> std::io::Result<()> {
+        let mut writer = stream;
+        for line in BufReader::new(stream).lines() {
+            let response = match serde_json::from_str::<Request>(&line?) {
+                Ok(request) => {
+                    if verbose {
+                        eprintln!(
+                            "Compiling {}",
+                            request.expression.as_deref().unwrap_or("<source>")
+                        );
+                    }
+                    // A panicked compile leaves nothing half-written worth guarding
+                    let _guard = compile_lock
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner);
+                    match compiler
+                        .clone()
+                        .with_error_format(request.error_format)
+                        .compile_and_cache(&request.source, cache, request.expression.as_deref())
+                    {
+                        Ok(result) => Response::Compiled {
+                            binary_path: result.binary_path,
+                            cache_hit: result.cache_hit,
+                        },
+                        Err(LobError::Compilation(msg)) => Response::Failed { error: msg },
+                        Err(e) => Response::Failed {
+                            error: format!("Error: {}", e),
+                        },
+                    }
+                }
+                Err(e) => Response::Failed {
+                    error: format!("Error: malformed request: {}", e),
+                },
+            };
+            serde_json::to_writer(&mut writer, &response)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Compile through the daemon when `LOB_DAEMON=1` and one is running
+    ///
+    /// Returns `None` when the daemon is not enabled or not reachable, in
+    /// which case the caller compiles locally.
+    pub fn compile(
+        cache: &Cache,
+        source: &str,
+        expression: &str,
+        error_format: ErrorFormat,
+        verbose: bool,
+    ) -> Option<Result<CompileResult>> {
+        if std::env::var(ENV_VAR).ok()? != "1" {
+            return None;
+        }
+        let stream = match UnixStream::connect(socket_path(cache)) {
+            Ok(stream) => stream,
+            Err(e) => {
+                if verbose {
+                    eprintln!("Daemon not reachable ({}), compiling locally", e);
+                }
+                return None;
+            }
+        };
+        if verbose {
+            eprintln!("Compiling via daemon");
+        }
+        Some(request(&stream, source, expression, error_format))
+    }
+
+    /// Send one request over an open connection and wait for the reply
+    fn request(
+        stream: &UnixStream,
+        source: &str,
+        expression: &str,
+        error_format: ErrorFormat,
+    ) -> Result<CompileResult> {
+        let request = Request {
+            source: source.to_string(),
+            expression: Some(expression.to_string()),
+            error_format,
+        };
+        let mut writer = stream;
+        let mut line = serde_json::to_string(&request).map_err(|e| daemon_error(&e))?;
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply)?;
+        match serde_json::from_str(&reply).map_err(|e| daemon_error(&e))? {
+            Response::Compiled {
+                binary_path,
+                cache_hit,
+            } => Ok(CompileResult {
+                binary_path,
+                cache_hit,
+            }),
+            Response::Failed { error } => Err(LobError::Compilation(error)),
+        }
+    }
+
+    fn daemon_error(e: &serde_json::Error) -> LobError {
+        LobError::Io(std::io::Error::other(format!(
+            "daemon protocol error: {}",
+            e
+        )))
+    }
+}
+
+#[cfg(not(unix))]
+mod fallback {
+    use crate::cache::Cache;
+    use crate::compile::CompileResult;
+    use crate::error::{ErrorFormat, LobError, Result};
+
+    /// The daemon needs Unix domain sockets
+    pub fn serve(_verbose: bool) -> Result<()> {
+        Err(LobError::InvalidExpression(
+            "--daemon is only supported on Unix".to_string(),
+        ))
+    }
+
+    /// Without Unix sockets every compile happens locally
+    pub fn compile(
+        _cache: &Cache,
+        _source: &str,
+        _expression: &str,
+        _error_format: ErrorFormat,
+        _verbose: bool,
+    ) -> Option<Result<CompileResult>> {
+        None
+    }
+}
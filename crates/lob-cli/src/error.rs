@@ -2,6 +2,7 @@
 
 use crate::suggestion;
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Errors that can occur during lob execution
@@ -26,14 +27,81 @@ pub enum LobError {
     /// Invalid expression
     #[error("Invalid expression: {0}")]
     InvalidExpression(String),
+
+    /// The compiled program ran past `--exec-timeout` and was killed
+    #[error("Execution timed out after {0}s (--exec-timeout); the program was stopped")]
+    ExecutionTimeout(u64),
 }
 
 /// Result type for lob operations
 pub type Result<T> = std::result::Result<T, LobError>;
 
+/// How compilation errors are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// Colorized report for terminals
+    #[default]
+    Human,
+    /// One JSON object, for editors and CI
+    Json,
+}
+
+impl ErrorFormat {
+    /// Parse from string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A compilation failure, independent of how it is rendered
+#[derive(Debug, Serialize)]
+pub struct CompilationReport {
+    /// Description of the problem, if it matches a known mistake
+    pub problem: Option<String>,
+    /// Suggested fixes for the problem
+    pub fixes: Vec<String>,
+    /// The rustc diagnostics, line by line
+    pub rustc: Vec<String>,
+}
+
+impl CompilationReport {
+    /// Build a report from rustc's stderr
+    pub fn new(stderr: &str, user_expression: Option<&str>) -> Self {
+        let (problem, fixes) = match suggestion::get_suggestion(stderr, user_expression) {
+            Some(sug) => (Some(sug.problem), sug.fixes),
+            None => (None, Vec::new()),
+        };
+        Self {
+            problem,
+            fixes,
+            rustc: stderr.lines().map(String::from).collect(),
+        }
+    }
+}
+
 impl LobError {
-    /// Format a compilation error with colors and context
-    pub fn format_compilation_error(stderr: &str, user_expression: Option<&str>) -> String {
+    /// Format a compilation error in the requested format
+    pub fn format_compilation_error(
+        stderr: &str,
+        user_expression: Option<&str>,
+        format: ErrorFormat,
+    ) -> String {
+        let report = CompilationReport::new(stderr, user_expression);
+        match format {
+            ErrorFormat::Human => Self::format_human(&report, user_expression),
+            ErrorFormat::Json => {
+                serde_json::to_string(&report).expect("a report of strings always serializes")
+            }
+        }
+    }
+
+    /// Format a compilation report with colors and context
+    fn format_human(report: &CompilationReport, user_expression: Option<&str>) -> String {
         let mut output = Vec::new();
 
         // Header
@@ -51,19 +119,19 @@ impl LobError {
         }
 
         // Show helpful suggestions for common errors
-        if let Some(sug) = suggestion::get_suggestion(stderr, user_expression) {
+        if let Some(problem) = &report.problem {
             output.push(format!("  {}", "Problem:".red().bold()));
-            output.push(format!("    {}", sug.problem));
+            output.push(format!("    {}", problem));
             output.push(String::new());
             output.push(format!("  {}", "How to fix:".blue().bold()));
-            for fix in sug.fixes {
+            for fix in &report.fixes {
                 output.push(format!("    • {}", fix));
             }
             output.push(String::new());
         }
 
         // Parse rustc error output
-        let lines: Vec<&str> = stderr.lines().collect();
+        let lines: Vec<&str> = report.rustc.iter().map(String::as_str).collect();
         let mut i = 0;
 
         while i < lines.len() {
@@ -168,7 +236,8 @@ mod tests {
     #[test]
     fn format_error_with_user_expression() {
         let stderr = "error: expected `;`";
-        let formatted = LobError::format_compilation_error(stderr, Some("_.map(|x| x"));
+        let formatted =
+            LobError::format_compilation_error(stderr, Some("_.map(|x| x"), ErrorFormat::Human);
         assert!(formatted.contains("Your expression:"));
         assert!(formatted.contains("error: expected `;`"));
     }
@@ -176,7 +245,7 @@ mod tests {
     #[test]
     fn format_error_without_user_expression() {
         let stderr = "error: something went wrong";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(!formatted.contains("Your expression:"));
         assert!(formatted.contains("error: something went wrong"));
     }
@@ -184,14 +253,14 @@ mod tests {
     #[test]
     fn format_error_warning_header() {
         let stderr = "warning: unused variable";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(formatted.contains("warning: unused variable"));
     }
 
     #[test]
     fn format_error_location_simplified() {
         let stderr = "  --> /path/to/file.rs:10:5";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(formatted.contains("file.rs:10:5"));
         assert!(!formatted.contains("/path/to/"));
     }
@@ -199,14 +268,14 @@ mod tests {
     #[test]
     fn format_error_location_fallback() {
         let stderr = "  --> invalid-path-format";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(formatted.contains("invalid-path-format"));
     }
 
     #[test]
     fn format_error_code_and_caret_lines() {
         let stderr = "error: test\n 1 | let x = y;\n     ^^^^^^";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(formatted.contains("let x = y;"));
         assert!(formatted.contains("^^^^^^"));
     }
@@ -214,15 +283,32 @@ mod tests {
     #[test]
     fn format_error_help_and_note() {
         let stderr = "  = help: try this\n  = note: some context";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(formatted.contains("= help: try this"));
         assert!(formatted.contains("= note: some context"));
     }
 
+    #[test]
+    fn format_error_json() {
+        let stderr = "error[E0599]: no method named `foo` found\n  --> main.rs:3:5";
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&formatted).unwrap();
+        assert_eq!(
+            value["rustc"],
+            serde_json::json!([
+                "error[E0599]: no method named `foo` found",
+                "  --> main.rs:3:5"
+            ])
+        );
+        assert!(value["problem"].is_null() || value["problem"].is_string());
+        assert!(value["fixes"].is_array());
+        assert!(!formatted.contains('\u{1b}'));
+    }
+
     #[test]
     fn format_error_summary_lines() {
         let stderr = "error: aborting due to 2 previous errors";
-        let formatted = LobError::format_compilation_error(stderr, None);
+        let formatted = LobError::format_compilation_error(stderr, None, ErrorFormat::Human);
         assert!(formatted.contains("aborting due to"));
     }
 }
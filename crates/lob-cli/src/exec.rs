@@ -0,0 +1,44 @@
+//! Running compiled pipeline binaries
+
+use crate::error::{LobError, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+/// How often a running binary is checked against its `--exec-timeout`
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Run the compiled binary over the given input files
+///
+/// With a `timeout`, the binary is killed once it has run that long and
+/// [`LobError::ExecutionTimeout`] is returned.
+pub fn run_binary(
+    binary: &Path,
+    files: &[PathBuf],
+    stdin: Stdio,
+    timeout: Option<Duration>,
+) -> Result<ExitStatus> {
+    let mut child = Command::new(binary)
+        .args(files)
+        .stdin(stdin)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+    let Some(timeout) = timeout else {
+        return Ok(child.wait()?);
+    };
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            // The child may exit on its own between the check and the kill
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(LobError::ExecutionTimeout(timeout.as_secs()));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
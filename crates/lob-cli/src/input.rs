@@ -12,8 +12,12 @@ pub enum InputFormat {
     Csv,
     /// TSV with headers
     Tsv,
-    /// JSON lines (one JSON object per line)
+    /// JSON lines (one JSON object per line), or a pretty-printed document
     JsonLines,
+    /// A single JSON array (one item per element)
+    JsonArray,
+    /// Apache/Nginx common or combined access log lines
+    Logs,
 }
 
 /// Input source configuration
@@ -8,23 +8,26 @@
 mod cache;
 mod codegen;
 mod compile;
+mod daemon;
 mod error;
+mod exec;
 mod input;
 mod output;
+mod repl;
 mod suggestion;
 mod toolchain;
+mod watch;
 mod welcome;
 
 use cache::Cache;
-use clap::Parser;
+use clap::{ArgGroup, Parser};
 use codegen::CodeGenerator;
 use compile::Compiler;
-use error::{LobError, Result};
+use error::{ErrorFormat, LobError, Result};
 use input::{InputFormat, InputSource};
-use output::OutputFormat;
+use output::{OutputFormat, TableStyle};
 use std::io::IsTerminal;
 use std::path::PathBuf;
-use std::process::Command;
 use toolchain::EmbeddedToolchain;
 
 /// Lob - Rust Pipeline Tool
@@ -32,11 +35,17 @@ use toolchain::EmbeddedToolchain;
 #[command(name = "lob")]
 #[command(about = "Run Rust data pipeline one-liners", long_about = None)]
 #[command(version)]
+#[command(group(ArgGroup::new("header_input").args(["parse_csv", "parse_tsv", "parse_logs"]).multiple(true)))]
+#[command(group(ArgGroup::new("json_input").args(["parse_json", "parse_json_array"]).multiple(true)))]
 struct Args {
     /// Lob expression to execute
-    #[arg(value_name = "EXPRESSION", required_unless_present_any = ["show_source", "clear_cache", "cache_stats"])]
+    #[arg(value_name = "EXPRESSION", required_unless_present_any = ["show_source", "clear_cache", "cache_stats", "repl", "daemon", "expr_file"])]
     expression: Option<String>,
 
+    /// Read the expression from a file instead of the command line
+    #[arg(long, value_name = "PATH", conflicts_with = "repl")]
+    expr_file: Option<PathBuf>,
+
     /// Input files (omit to read from stdin)
     #[arg(value_name = "FILE")]
     files: Vec<PathBuf>,
@@ -45,23 +54,132 @@ struct Args {
     #[arg(long)]
     parse_csv: bool,
 
+    /// With --parse-csv, make numeric cells JSON numbers and true/false booleans
+    /// (row is `HashMap<String, serde_json::Value>`)
+    #[arg(long, requires = "parse_csv")]
+    typed: bool,
+
     /// Parse input as TSV with headers
     #[arg(long)]
     parse_tsv: bool,
 
-    /// Parse input as JSON lines
+    /// Parse input as JSON lines (pretty-printed JSON documents are detected)
     #[arg(long)]
     parse_json: bool,
 
-    /// Output format
+    /// Parse input as a single JSON array (each element is one item)
+    #[arg(long)]
+    parse_json_array: bool,
+
+    /// Replace each JSON record with the value at a dotted path, e.g. `user.address.city`
+    #[arg(long, value_name = "PATH", requires = "json_input")]
+    parse_json_path: Option<String>,
+
+    /// Parse input as Apache/Nginx access logs (row is `HashMap<String, String>`)
+    #[arg(long)]
+    parse_logs: bool,
+
+    /// Slice each line into fixed-width fields, e.g. `1-5,10-20` (row is `Vec<String>`)
+    #[arg(
+        long,
+        value_name = "SPEC",
+        value_parser = parse_fields_spec,
+        conflicts_with_all = ["parse_csv", "parse_tsv", "parse_json", "parse_json_array", "parse_logs"]
+    )]
+    fields: Option<String>,
+
+    /// Keep only these comma-separated columns of each CSV/TSV/log row
+    #[arg(
+        long,
+        value_name = "COLS",
+        value_delimiter = ',',
+        requires = "header_input"
+    )]
+    select: Vec<String>,
+
+    /// Keep only the first N items (like `head -n N`)
+    #[arg(long, value_name = "N", conflicts_with = "tail")]
+    head: Option<usize>,
+
+    /// Keep only the last N items (like `tail -n N`)
+    #[arg(long, value_name = "N")]
+    tail: Option<usize>,
+
+    /// Drop duplicate input records, like SQL `SELECT DISTINCT`
+    #[arg(long)]
+    distinct: bool,
+
+    /// Run a plain map/filter chain on N threads (collects input first; order is kept)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
+    /// Skip items whose processing panics (e.g. a failed unwrap) instead of aborting
+    #[arg(long)]
+    resilient: bool,
+
+    /// Print a running count of input records and throughput to stderr
+    #[arg(long)]
+    progress: bool,
+
+    /// After running, print the number of records emitted and the elapsed time to stderr
+    #[arg(long)]
+    summary: bool,
+
+    /// Decode line input from this encoding, e.g. latin1 or windows-1252
+    #[arg(long, value_name = "NAME", value_parser = parse_encoding)]
+    encoding: Option<String>,
+
+    /// Fail on the first unreadable or malformed input record instead of skipping it
+    #[arg(long)]
+    strict: bool,
+
+    /// Treat this CSV/TSV cell value as missing (repeatable), e.g. NA
+    #[arg(long = "null-value", value_name = "STR")]
+    null_values: Vec<String>,
+
+    /// Seed for randomized operations such as `sample`, for reproducible runs
+    #[arg(long, value_name = "U64")]
+    seed: Option<u64>,
+
+    /// Read at most N input records, before the expression runs
+    #[arg(long, value_name = "N")]
+    input_limit: Option<usize>,
+
+    /// Statement to run before the expression, e.g. `let threshold = 5;` (repeatable)
+    #[arg(long, value_name = "STMT")]
+    pre: Vec<String>,
+
+    /// Output format (`rust` formats the code shown by --show-source with rustfmt)
     #[arg(short = 'f', long, value_name = "FORMAT")]
-    #[arg(value_parser = ["debug", "json", "jsonl", "csv", "table"])]
+    #[arg(value_parser = ["debug", "json", "jsonl", "csv", "table", "toml", "yaml", "rust"])]
     format: Option<String>,
 
+    /// Print at most N records, noting on stderr when there were more
+    /// (default 1000 when stdout is a terminal; 0 means no limit)
+    #[arg(long, value_name = "N")]
+    max_output: Option<usize>,
+
+    /// Indent each record of jsonl output (json output is already indented)
+    #[arg(long)]
+    pretty: bool,
+
+    /// Leave the last debug/jsonl record without a terminating newline
+    #[arg(long)]
+    no_trailing_newline: bool,
+
+    /// Border style for table output
+    #[arg(long, value_name = "STYLE", default_value = "rounded")]
+    #[arg(value_parser = ["rounded", "ascii", "markdown", "sharp", "blank"])]
+    table_style: String,
+
     /// Show generated source code without executing
     #[arg(short = 's', long)]
     show_source: bool,
 
+    /// Annotate the source shown by --show-source with explanatory comments
+    #[arg(long, requires = "show_source")]
+    explain: bool,
+
     /// Clear the compilation cache
     #[arg(long)]
     clear_cache: bool,
@@ -77,19 +195,130 @@ struct Args {
     /// Show performance statistics after execution
     #[arg(long)]
     stats: bool,
+
+    /// How to report compilation errors: a colorized report, or one JSON object
+    /// with `problem`, `fixes` and `rustc` fields for editors and CI
+    #[arg(long, value_name = "FORMAT", default_value = "human")]
+    #[arg(value_parser = ["human", "json"])]
+    error_format: String,
+
+    /// Kill the compiled program if it runs longer than this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(conflicts_with = "watch")]
+    exec_timeout: Option<u64>,
+
+    /// Re-run whenever the input files change (file input only)
+    #[arg(long)]
+    watch: bool,
+
+    /// Start an interactive prompt that runs each entered expression
+    #[arg(long, conflicts_with_all = ["show_source", "watch"])]
+    repl: bool,
+
+    /// Run a background compile server; set `LOB_DAEMON=1` to compile through it
+    #[arg(long, exclusive = true)]
+    daemon: bool,
+}
+
+impl Args {
+    /// Input format selected by the `--parse-*` flags
+    fn input_format(&self) -> InputFormat {
+        if self.parse_csv {
+            InputFormat::Csv
+        } else if self.parse_tsv {
+            InputFormat::Tsv
+        } else if self.parse_json {
+            InputFormat::JsonLines
+        } else if self.parse_json_array {
+            InputFormat::JsonArray
+        } else if self.parse_logs {
+            InputFormat::Logs
+        } else {
+            InputFormat::Lines
+        }
+    }
+
+    /// Input files to read
+    ///
+    /// When the expression comes from `--expr-file` or there is none
+    /// (`--repl`), clap puts the first file in the EXPRESSION slot.
+    fn input_files(&self) -> Vec<PathBuf> {
+        if self.expr_file.is_some() || self.repl {
+            self.expression
+                .iter()
+                .map(PathBuf::from)
+                .chain(self.files.iter().cloned())
+                .collect()
+        } else {
+            self.files.clone()
+        }
+    }
+}
+
+/// Read an `--expr-file`, dropping the trailing newline editors add
+fn read_expr_file(path: &std::path::Path) -> Result<String> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        LobError::InvalidExpression(format!(
+            "Cannot read expression file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok(text.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Run generated source through `rustfmt`, returning it unchanged if that fails
+fn rustfmt(source: String) -> String {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let Ok(mut child) = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return source;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // A write error shows up below as a failed exit status
+        let _ = stdin.write_all(source.as_bytes());
+    }
+    match child.wait_with_output() {
+        Ok(output) if output.status.success() => String::from_utf8(output.stdout)
+            .map(|formatted| formatted.trim_end().to_string())
+            .unwrap_or(source),
+        _ => source,
+    }
+}
+
+/// Validate a `--fields` spec at argument-parsing time
+fn parse_fields_spec(spec: &str) -> std::result::Result<String, String> {
+    lob_prelude::parse_field_spec(spec).map(|_| spec.to_string())
+}
+
+/// Resolve an `--encoding` label to its canonical name at argument-parsing time
+fn parse_encoding(label: &str) -> std::result::Result<String, String> {
+    lob_prelude::encoding_name(label).map(str::to_string)
 }
 
 fn main() {
     if let Err(e) = run() {
-        // Compilation errors are already formatted nicely
-        match &e {
-            LobError::Compilation(msg) => eprintln!("{}", msg),
-            _ => eprintln!("Error: {}", e),
-        }
+        print_error(&e);
         std::process::exit(1);
     }
 }
 
+/// Print an error to stderr
+pub(crate) fn print_error(e: &LobError) {
+    // Compilation errors are already formatted nicely
+    match e {
+        LobError::Compilation(msg) => eprintln!("{}", msg),
+        _ => eprintln!("Error: {}", e),
+    }
+}
+
 fn run() -> Result<()> {
     let args = Args::parse();
 
@@ -101,6 +330,10 @@ fn run() -> Result<()> {
         return Ok(());
     }
 
+    if args.daemon {
+        return daemon::serve(args.verbose);
+    }
+
     if args.cache_stats {
         let cache = Cache::new()?;
         let stats = cache.stats()?;
@@ -112,7 +345,7 @@ fn run() -> Result<()> {
     }
 
     // Show welcome message if no expression and stdin is a terminal
-    if args.expression.is_none() {
+    if args.expression.is_none() && args.expr_file.is_none() && !args.repl {
         if args.files.is_empty() && std::io::stdin().is_terminal() {
             welcome::print_welcome();
             return Ok(());
@@ -122,57 +355,196 @@ fn run() -> Result<()> {
         ));
     }
 
-    let expression = args.expression.unwrap();
+    let input_format = args.input_format();
 
-    // Determine input format
-    let input_format = if args.parse_csv {
-        InputFormat::Csv
-    } else if args.parse_tsv {
-        InputFormat::Tsv
-    } else if args.parse_json {
-        InputFormat::JsonLines
-    } else {
-        InputFormat::Lines
+    // `--format rust` formats the shown source rather than the program's output
+    let format_source = args.format.as_deref() == Some("rust");
+    if format_source && !args.show_source {
+        return Err(LobError::InvalidExpression(
+            "--format rust only applies to --show-source".to_string(),
+        ));
+    }
+
+    // Determine output format
+    let output_format = match args.format {
+        Some(ref fmt) if !format_source => OutputFormat::from_str(fmt).ok_or_else(|| {
+            LobError::InvalidExpression(format!("Unknown output format: {}", fmt))
+        })?,
+        _ => OutputFormat::default(output::is_terminal()),
+    };
+
+    validate_input_flags(&args, input_format)?;
+    warn_ignored_output_flags(&args, output_format);
+
+    let table_style = TableStyle::from_str(&args.table_style).ok_or_else(|| {
+        LobError::InvalidExpression(format!("Unknown table style: {}", args.table_style))
+    })?;
+
+    if args.repl {
+        let files = args.input_files();
+        InputSource::new(files.clone(), input_format).validate()?;
+        return repl::run(
+            files,
+            input_format,
+            args.verbose,
+            args.exec_timeout.map(std::time::Duration::from_secs),
+            |expression, input_source| {
+                build_generator(&args, expression, input_source, output_format, table_style)
+                    .generate()
+            },
+        );
+    }
+
+    let expression = match &args.expr_file {
+        Some(path) => read_expr_file(path)?,
+        None => args.expression.clone().unwrap(),
     };
 
     // Create input source
-    let input_source = InputSource::new(args.files.clone(), input_format);
+    let input_source = InputSource::new(args.input_files(), input_format);
     input_source.validate()?;
 
-    // Determine output format
-    let output_format = if let Some(ref fmt) = args.format {
-        OutputFormat::from_str(fmt)
-            .ok_or_else(|| LobError::InvalidExpression(format!("Unknown output format: {}", fmt)))?
-    } else {
-        OutputFormat::default(output::is_terminal())
-    };
+    if args.watch && input_source.is_stdin() {
+        return Err(LobError::InvalidExpression(
+            "--watch needs input files; it cannot watch stdin".to_string(),
+        ));
+    }
 
     // Generate code
-    let generator = CodeGenerator::new(
-        expression.clone(),
+    let generator = build_generator(
+        &args,
+        &expression,
         input_source.clone(),
         output_format,
-        args.stats,
+        table_style,
     );
+
+    if (args.head.is_some() || args.tail.is_some()) && generator.has_terminal_operation() {
+        eprintln!("Warning: --head/--tail ignored because the expression already ends in a terminal operation");
+    }
+
     let source = generator.generate()?;
 
     if args.show_source {
+        let source = if format_source {
+            rustfmt(source)
+        } else {
+            source
+        };
         println!("{}", source);
         return Ok(());
     }
 
     // Compile and execute
-    compile_and_execute(
-        &expression,
-        &source,
-        &input_source,
-        args.verbose,
+    compile_and_execute(&args, &expression, &source, &input_source)
+}
+
+/// Reject input flags that do not apply to the chosen input format
+fn validate_input_flags(args: &Args, input_format: InputFormat) -> Result<()> {
+    if !args.null_values.is_empty() && !matches!(input_format, InputFormat::Csv | InputFormat::Tsv)
+    {
+        return Err(LobError::InvalidExpression(
+            "--null-value requires --parse-csv or --parse-tsv".to_string(),
+        ));
+    }
+
+    if args.encoding.is_some() && (args.fields.is_some() || input_format != InputFormat::Lines) {
+        return Err(LobError::InvalidExpression(
+            "--encoding applies to plain line input only".to_string(),
+        ));
+    }
+
+    if args.strict
+        && (args.fields.is_some()
+            || args.encoding.is_some()
+            || matches!(input_format, InputFormat::JsonArray | InputFormat::Logs))
+    {
+        return Err(LobError::InvalidExpression(
+            "--strict supports plain lines (without --fields or --encoding), --parse-csv, --parse-tsv and --parse-json input"
+                .to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Warn about output flags that do not apply to the chosen format
+fn warn_ignored_output_flags(args: &Args, output_format: OutputFormat) {
+    if args.pretty && !matches!(output_format, OutputFormat::Json | OutputFormat::JsonLines) {
+        eprintln!("Warning: --pretty ignored because the output format is not json or jsonl");
+    }
+
+    if args.no_trailing_newline
+        && !matches!(output_format, OutputFormat::Debug | OutputFormat::JsonLines)
+    {
+        eprintln!(
+            "Warning: --no-trailing-newline ignored because the output format is not debug or jsonl"
+        );
+    }
+}
+
+/// Records printed by default when stdout is a terminal
+const TERMINAL_MAX_OUTPUT: usize = 1000;
+
+/// Resolve `--max-output`: explicit value, else a cap for terminals only
+fn max_output(args: &Args) -> Option<usize> {
+    args.max_output
+        .or_else(|| output::is_terminal().then_some(TERMINAL_MAX_OUTPUT))
+        .filter(|&n| n > 0)
+}
+
+/// Build the code generator for an expression from the command-line options
+fn build_generator(
+    args: &Args,
+    expression: &str,
+    input_source: InputSource,
+    output_format: OutputFormat,
+    table_style: TableStyle,
+) -> CodeGenerator {
+    let generator = CodeGenerator::new(
+        expression.to_string(),
+        input_source,
+        output_format,
         args.stats,
     )
+    .with_select(args.select.clone())
+    .with_head(args.head)
+    .with_tail(args.tail)
+    .with_input_limit(args.input_limit)
+    .with_explain(args.explain)
+    .with_table_style(table_style)
+    .with_fields(args.fields.clone())
+    .with_pre(args.pre.clone())
+    .with_distinct(args.distinct)
+    .with_json_path(args.parse_json_path.clone())
+    .with_progress(args.progress)
+    .with_seed(args.seed)
+    .with_null_values(args.null_values.clone())
+    .with_strict(args.strict)
+    .with_typed(args.typed)
+    .with_encoding(args.encoding.clone())
+    .with_summary(args.summary)
+    .with_pretty(args.pretty)
+    .with_trailing_newline(!args.no_trailing_newline)
+    .with_jobs(args.jobs.map(usize::from))
+    .with_max_output(max_output(args))
+    .with_resilient(args.resilient);
+
+    if args.jobs.is_some() && !generator.is_parallelizable() {
+        eprintln!(
+            "Warning: --jobs ignored because the expression is not a plain map/filter chain on `_`"
+        );
+    }
+    if args.resilient && generator.has_terminal_operation() {
+        eprintln!(
+            "Warning: --resilient ignored because the expression ends in a terminal operation"
+        );
+    }
+    generator
 }
 
 /// Initialize the compiler, trying embedded toolchain first, then system rustc
-fn initialize_compiler(verbose: bool) -> Result<Compiler> {
+pub(crate) fn initialize_compiler(verbose: bool) -> Result<Compiler> {
     match EmbeddedToolchain::ensure_extracted() {
         Ok(toolchain) if toolchain.is_valid() => {
             if verbose {
@@ -197,21 +569,26 @@ fn initialize_compiler(verbose: bool) -> Result<Compiler> {
 
 /// Compile the generated source and execute the resulting binary
 fn compile_and_execute(
+    args: &Args,
     expression: &str,
     source: &str,
     input_source: &InputSource,
-    verbose: bool,
-    show_stats: bool,
 ) -> Result<()> {
+    let verbose = args.verbose;
+    let error_format = ErrorFormat::from_str(&args.error_format).unwrap_or_default();
     let cache = Cache::new()?;
-    let compiler = initialize_compiler(verbose)?;
 
     if verbose {
         eprintln!("Compiling expression...");
     }
 
     let compile_start = std::time::Instant::now();
-    let compile_result = compiler.compile_and_cache(source, &cache, Some(expression))?;
+    let compile_result = match daemon::compile(&cache, source, expression, error_format, verbose) {
+        Some(result) => result?,
+        None => initialize_compiler(verbose)?
+            .with_error_format(error_format)
+            .compile_and_cache(source, &cache, Some(expression))?,
+    };
     let compile_time = compile_start.elapsed();
 
     if verbose {
@@ -222,30 +599,29 @@ fn compile_and_execute(
 
     // Execute the compiled binary
     let exec_start = std::time::Instant::now();
-    let mut cmd = Command::new(&compile_result.binary_path);
-
-    if !input_source.is_stdin() {
-        cmd.args(&input_source.files);
-    }
-
-    let mut child = cmd
-        .stdin(std::process::Stdio::inherit())
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .spawn()?;
-
-    let status = child.wait()?;
+    let files: &[PathBuf] = if input_source.is_stdin() {
+        &[]
+    } else {
+        &input_source.files
+    };
+    let exec_timeout = args.exec_timeout.map(std::time::Duration::from_secs);
+    let status = exec::run_binary(
+        &compile_result.binary_path,
+        files,
+        std::process::Stdio::inherit(),
+        exec_timeout,
+    )?;
     let exec_time = exec_start.elapsed();
     let total_time = compile_start.elapsed();
 
-    if !status.success() {
+    if !status.success() && !args.watch {
         return Err(LobError::Compilation(format!(
             "Execution failed with status: {}",
             status
         )));
     }
 
-    if show_stats {
+    if args.stats {
         eprintln!();
         eprintln!("Statistics:");
         eprintln!("  Compilation time: {:?}", compile_time);
@@ -261,5 +637,12 @@ fn compile_and_execute(
         );
     }
 
+    if args.watch {
+        if verbose {
+            eprintln!("Watching {} input file(s) for changes...", files.len());
+        }
+        return watch::watch_inputs(&compile_result.binary_path, files, output::is_terminal());
+    }
+
     Ok(())
 }
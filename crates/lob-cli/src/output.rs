@@ -15,6 +15,10 @@ pub enum OutputFormat {
     Csv,
     /// Table (requires CSV/JSON input)
     Table,
+    /// TOML document (iterators become an array of tables)
+    Toml,
+    /// YAML document (iterators become a sequence)
+    Yaml,
 }
 
 impl OutputFormat {
@@ -26,6 +30,8 @@ impl OutputFormat {
             "jsonl" | "jsonlines" => Some(Self::JsonLines),
             "csv" => Some(Self::Csv),
             "table" => Some(Self::Table),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
             _ => None,
         }
     }
@@ -40,6 +46,47 @@ impl OutputFormat {
     }
 }
 
+/// Border style for table output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableStyle {
+    /// Unicode box drawing with rounded corners (default)
+    #[default]
+    Rounded,
+    /// Plain ASCII `+---+` borders
+    Ascii,
+    /// GitHub-flavored markdown table
+    Markdown,
+    /// Unicode box drawing with square corners
+    Sharp,
+    /// No borders, whitespace-separated columns
+    Blank,
+}
+
+impl TableStyle {
+    /// Parse from string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "rounded" => Some(Self::Rounded),
+            "ascii" => Some(Self::Ascii),
+            "markdown" => Some(Self::Markdown),
+            "sharp" => Some(Self::Sharp),
+            "blank" => Some(Self::Blank),
+            _ => None,
+        }
+    }
+
+    /// The `tabled::settings::Style` constructor for this style
+    pub fn constructor(self) -> &'static str {
+        match self {
+            Self::Rounded => "Style::rounded()",
+            Self::Ascii => "Style::ascii()",
+            Self::Markdown => "Style::markdown()",
+            Self::Sharp => "Style::sharp()",
+            Self::Blank => "Style::blank()",
+        }
+    }
+}
+
 /// Detect if stdout is a terminal
 pub fn is_terminal() -> bool {
     stdout().is_terminal()
@@ -0,0 +1,118 @@
+//! Interactive prompt that compiles and runs one expression per line
+
+use crate::cache::Cache;
+use crate::error::{LobError, Result};
+use crate::input::{InputFormat, InputSource};
+use std::fs::File;
+use std::io::{BufRead, BufReader, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+const PROMPT: &str = "lob> ";
+
+/// Buffered stdin, removed again when the REPL exits
+struct StdinBuffer(PathBuf);
+
+impl Drop for StdinBuffer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Run the REPL until `:quit` or end of input
+///
+/// Expressions run against `files`. Without files, piped stdin is read once
+/// up front and expressions are then read from the terminal instead. Each
+/// run is stopped after `exec_timeout`, if given.
+pub fn run<F>(
+    mut files: Vec<PathBuf>,
+    format: InputFormat,
+    verbose: bool,
+    exec_timeout: Option<Duration>,
+    generate: F,
+) -> Result<()>
+where
+    F: Fn(&str, InputSource) -> Result<String>,
+{
+    let cache = Cache::new()?;
+    let compiler = crate::initialize_compiler(verbose)?;
+
+    let (prompt, _buffer): (Box<dyn BufRead>, _) =
+        if files.is_empty() && !std::io::stdin().is_terminal() {
+            let path = cache
+                .cache_dir()
+                .join(format!("repl-stdin-{}", std::process::id()));
+            std::io::copy(&mut std::io::stdin().lock(), &mut File::create(&path)?)?;
+            files.push(path.clone());
+            let tty = BufReader::new(File::open("/dev/tty")?);
+            (Box::new(tty), Some(StdinBuffer(path)))
+        } else {
+            (Box::new(std::io::stdin().lock()), None)
+        };
+
+    let mut last_source: Option<String> = None;
+    let mut lines = prompt.lines();
+    loop {
+        eprint!("{}", PROMPT);
+        std::io::stderr().flush()?;
+
+        let Some(line) = lines.next() else {
+            eprintln!();
+            return Ok(());
+        };
+        let line = line?;
+        let input = line.trim();
+
+        match input {
+            "" => continue,
+            ":quit" | ":q" => return Ok(()),
+            ":clear" => {
+                print!("\x1B[2J\x1B[H");
+                std::io::stdout().flush()?;
+                continue;
+            }
+            ":source" => {
+                match &last_source {
+                    Some(source) => println!("{}", source),
+                    None => eprintln!("No expression has been run yet"),
+                }
+                continue;
+            }
+            _ if input.starts_with(':') => {
+                eprintln!("Unknown command: {} (try :source, :clear or :quit)", input);
+                continue;
+            }
+            _ => {}
+        }
+
+        let input_source = InputSource::new(files.clone(), format);
+        let result = generate(input, input_source).and_then(|source| {
+            let compile_result = compiler.compile_and_cache(&source, &cache, Some(input));
+            last_source = Some(source);
+            let compile_result = compile_result?;
+            if verbose {
+                eprintln!("Cache hit: {}", compile_result.cache_hit);
+            }
+
+            let status = crate::exec::run_binary(
+                &compile_result.binary_path,
+                &files,
+                Stdio::null(),
+                exec_timeout,
+            )?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(LobError::Compilation(format!(
+                    "Execution failed with status: {}",
+                    status
+                )))
+            }
+        });
+
+        if let Err(e) = result {
+            crate::print_error(&e);
+        }
+    }
+}
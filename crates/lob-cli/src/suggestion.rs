@@ -26,6 +26,18 @@ pub fn get_suggestion(stderr: &str, user_expr: Option<&str>) -> Option<ErrorSugg
         });
     }
 
+    // unique() / --distinct on items that can't be hashed (e.g. CSV rows)
+    if stderr.contains("unique") && stderr.contains("Hash") {
+        return Some(ErrorSuggestion {
+            problem: "Items must implement Eq + Hash + Clone to remove duplicates".to_string(),
+            fixes: vec![
+                "HashMap rows (CSV/TSV/logs) cannot be hashed; map them to a key first".to_string(),
+                "Deduplicate on a column: _.map(|r| r[\"name\"].clone()).unique()".to_string(),
+                "Floats are not Hash; deduplicate on x.to_string() instead".to_string(),
+            ],
+        });
+    }
+
     // Cannot find function
     if stderr.contains("cannot find function") {
         if let Some(expr) = user_expr {
@@ -0,0 +1,66 @@
+//! Re-run a compiled pipeline whenever its input files change
+
+use crate::error::{LobError, Result};
+use crate::exec::run_binary;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long to wait for a burst of file events to settle before re-running
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Block forever, re-running `binary` each time one of `files` changes
+///
+/// The parent directories are watched rather than the files themselves so
+/// that editors which save by replacing the file are still picked up.
+pub fn watch_inputs(binary: &Path, files: &[PathBuf], clear_screen: bool) -> Result<()> {
+    let targets: HashSet<PathBuf> = files
+        .iter()
+        .map(|f| f.canonicalize())
+        .collect::<std::io::Result<_>>()?;
+    let dirs: HashSet<&Path> = targets.iter().filter_map(|f| f.parent()).collect();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| watch_error(&e))?;
+    for dir in dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| watch_error(&e))?;
+    }
+
+    let is_relevant = |event: &notify::Event| {
+        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event.paths.iter().any(|p| targets.contains(p))
+    };
+
+    loop {
+        let event = rx
+            .recv()
+            .map_err(|_| LobError::Io(std::io::Error::other("file watcher stopped")))?;
+        if !event.as_ref().is_ok_and(is_relevant) {
+            continue;
+        }
+        // Swallow the rest of the burst (an editor save is often several events)
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+        if clear_screen {
+            print!("\x1B[2J\x1B[H");
+            std::io::stdout().flush()?;
+        }
+        let status = run_binary(binary, files, Stdio::inherit(), None)?;
+        if !status.success() {
+            eprintln!("Execution failed with status: {}", status);
+        }
+    }
+}
+
+fn watch_error(e: &notify::Error) -> LobError {
+    LobError::Io(std::io::Error::other(format!(
+        "failed to watch input files: {}",
+        e
+    )))
+}
@@ -39,7 +39,7 @@ pub fn print_welcome() {
     println!("    {}  map, enumerate, zip, flatten", "Transform: ".cyan());
     println!("    {}  chunk, window, group_by", "Grouping:  ".cyan());
     println!(
-        "    {}  count, sum, min, max, to_list",
+        "    {}  count, sum, min, max, to_list, collect_strings, collect_set",
         "Terminal:  ".cyan()
     );
     println!();
@@ -48,6 +48,14 @@ pub fn print_welcome() {
     println!("    --parse-csv         Parse input as CSV with headers");
     println!("    --parse-tsv         Parse input as TSV with headers");
     println!("    --parse-json        Parse each line as JSON");
+    println!("    --parse-json-array  Parse input as one JSON array");
+    println!("    --parse-json-path a.b  Keep the value at a dotted JSON path");
+    println!("    --parse-logs        Parse Apache/Nginx access logs");
+    println!("    --fields 1-5,10-20  Slice fixed-width lines into fields");
+    println!("    --select a,b        Keep only these CSV/TSV/log columns");
+    println!("    --head N / --tail N Keep only the first/last N items");
+    println!("    --input-limit N     Read at most N input records");
+    println!("    --distinct          Drop duplicate input records");
     println!();
 
     println!("{}", "OUTPUT FORMATS:".bold());
@@ -56,12 +64,18 @@ pub fn print_welcome() {
     println!("    --format jsonl      JSON lines (one per line)");
     println!("    --format csv        CSV output (requires CSV input)");
     println!("    --format table      Table output (requires CSV/JSON input)");
+    println!("    --format toml       TOML document");
+    println!("    --format yaml       YAML document");
+    println!("    --table-style ascii Table borders: rounded, ascii, markdown, sharp, blank");
     println!();
 
     println!("{}", "LEARN MORE:".bold());
     println!("    lob --help              Full documentation");
     println!("    lob --show-source EXPR  See generated Rust code");
+    println!("    lob --show-source --explain EXPR  Annotated generated code");
     println!("    lob --cache-stats       View compilation cache");
+    println!("    lob --watch EXPR FILE   Re-run whenever FILE changes");
+    println!("    lob --repl FILE         Interactive prompt (:source, :clear, :quit)");
     println!();
 
     println!("{}", "https://github.com/olirice/lob".dimmed());
@@ -238,6 +238,17 @@ fn count() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn collect_set_is_terminal() -> Result<()> {
+    lob()
+        .args(["-f", "json", "_.map(|l| l.len()).collect_set().len()"])
+        .write_stdin("ab\ncd\nefg\n")
+        .assert()
+        .success()
+        .stdout("2\n");
+    Ok(())
+}
+
 #[test]
 fn sum() -> Result<()> {
     lob()
@@ -271,6 +282,72 @@ fn max() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn min_max() -> Result<()> {
+    lob()
+        .arg("_.map(|x| x.parse::<i32>().unwrap()).min_max()")
+        .write_stdin("3\n1\n4\n1\n5\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[1,5]"));
+    Ok(())
+}
+
+#[test]
+fn histogram() -> Result<()> {
+    lob()
+        .arg("_.map(|x| x.parse::<f64>().unwrap()).histogram(2, 0.0, 10.0)")
+        .write_stdin("1\n2\n3\n8\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[3,1]"));
+    Ok(())
+}
+
+#[test]
+fn positions() -> Result<()> {
+    lob()
+        .arg("_.positions(|l| l.contains(\"ERROR\"))")
+        .write_stdin("ok\nERROR a\nok\nERROR b\n")
+        .assert()
+        .success()
+        .stdout("[1,3]\n");
+    Ok(())
+}
+
+#[test]
+fn mode() -> Result<()> {
+    lob()
+        .arg("_.mode()")
+        .write_stdin("red\nblue\nblue\ngreen\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"blue\""));
+    Ok(())
+}
+
+#[test]
+fn argmax() -> Result<()> {
+    lob()
+        .arg("_.map(|x| x.parse::<i32>().unwrap()).argmax()")
+        .write_stdin("3\n1\n9\n4\n")
+        .assert()
+        .success()
+        .stdout("2\n");
+    Ok(())
+}
+
+#[test]
+fn percentile() -> Result<()> {
+    lob()
+        .arg("_.map(|x| x.parse::<f64>().unwrap()).percentile(50.0)")
+        .write_stdin("4\n1\n3\n2\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2.5"));
+    Ok(())
+}
+
 #[test]
 fn first() -> Result<()> {
     lob()
@@ -293,6 +370,17 @@ fn last() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn take_last() -> Result<()> {
+    lob()
+        .arg("_.take_last(2)")
+        .write_stdin("a\nb\nc\nd\n")
+        .assert()
+        .success()
+        .stdout("[\"c\",\"d\"]\n");
+    Ok(())
+}
+
 #[test]
 fn collect() -> Result<()> {
     lob()
@@ -335,6 +423,97 @@ fn fold() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn try_map() -> Result<()> {
+    lob()
+        .arg("_.try_map(|x| x.parse::<i32>().map_err(|e| e.to_string()))")
+        .write_stdin("1\n2\n3\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("{\"Ok\":[1,2,3]}"));
+    Ok(())
+}
+
+#[test]
+fn try_collect_prints_result() -> Result<()> {
+    lob()
+        .args([
+            "-f",
+            "debug",
+            "_.map(|x| x.parse::<i32>()).try_collect::<Vec<_>, _, _>()",
+        ])
+        .write_stdin("1\n2\n3\n")
+        .assert()
+        .success()
+        .stdout("Ok([1, 2, 3])\n");
+    Ok(())
+}
+
+#[test]
+fn group_by_agg() -> Result<()> {
+    lob()
+        .arg(r#"lob(vec![1,2,3,4,5,6]).group_by_agg(|x| if x % 2 == 0 { "even" } else { "odd" }, || 0, |acc, x| acc + x)"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"even\":12"))
+        .stdout(predicate::str::contains("\"odd\":9"));
+    Ok(())
+}
+
+#[test]
+fn find_map() -> Result<()> {
+    lob()
+        .arg(r#"_.find_map(|l| l.strip_prefix("version=").map(str::to_string))"#)
+        .write_stdin("name=lob\nversion=1.2.3\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"1.2.3\""));
+    Ok(())
+}
+
+#[test]
+fn partition_by() -> Result<()> {
+    lob()
+        .arg(r#"_.partition_by(|x| if x.starts_with('E') { "errors" } else { "other" })"#)
+        .write_stdin("ERROR a\nINFO b\nERROR c\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"errors\":[\"ERROR a\",\"ERROR c\"]",
+        ))
+        .stdout(predicate::str::contains("\"other\":[\"INFO b\"]"));
+    Ok(())
+}
+
+#[test]
+fn max_by_group() -> Result<()> {
+    let f = temp("csv", "endpoint,ms\n/a,120\n/b,40\n/a,310\n");
+    lob()
+        .arg("--parse-csv")
+        .arg(r#"_.max_by_group(|r| r["endpoint"].clone(), |r| r["ms"].parse::<u32>().unwrap())"#)
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"/a\":310"))
+        .stdout(predicate::str::contains("\"/b\":40"));
+    Ok(())
+}
+
+#[test]
+fn split_results() -> Result<()> {
+    lob()
+        .args([
+            "--format",
+            "debug",
+            "_.map(|x| x.parse::<i32>()).split_results()",
+        ])
+        .write_stdin("1\noops\n3\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("([1, 3], [ParseIntError"));
+    Ok(())
+}
+
 #[test]
 fn any() -> Result<()> {
     lob()
@@ -355,176 +534,1004 @@ fn all() -> Result<()> {
     Ok(())
 }
 
-// ── Input formats ────────────────────────────────────────────────
+#[test]
+fn enumerate_from() -> Result<()> {
+    lob()
+        .args(["--format", "jsonl", "_.enumerate_from(1)"])
+        .write_stdin("first\nsecond\n")
+        .assert()
+        .success()
+        .stdout("[1,\"first\"]\n[2,\"second\"]\n");
+    Ok(())
+}
 
 #[test]
-fn stdin_input() -> Result<()> {
+fn flat_map_split_lines_on() -> Result<()> {
     lob()
-        .arg("_.count()")
-        .write_stdin("hello\nworld\n")
+        .arg("_.flat_map(split_lines_on(';'))")
+        .write_stdin("a;b\nc;;d;\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("2"));
+        .stdout("\"a\"\n\"b\"\n\"c\"\n\"d\"\n");
     Ok(())
 }
 
 #[test]
-fn file_input() -> Result<()> {
-    let f = temp("txt", "hello\nworld\ntest\n");
+fn filter_matches_regex() -> Result<()> {
     lob()
-        .arg("_.filter(|x| x.len() > 4)")
-        .arg(f.path())
+        .arg("_.filter(matches(\"^(ERROR|WARN) \"))")
+        .write_stdin("ERROR disk full\nINFO started\nWARN low memory\nDEBUG ERROR x\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"hello\""))
-        .stdout(predicate::str::contains("\"world\""));
+        .stdout("\"ERROR disk full\"\n\"WARN low memory\"\n");
     Ok(())
 }
 
 #[test]
-fn multi_file_input() -> Result<()> {
-    let f1 = temp("txt", "a\nb\nc\n");
-    let f2 = temp("txt", "c\nd\ne\n");
+fn filter_map_capture_regex() -> Result<()> {
     lob()
-        .arg("_.unique().count()")
-        .arg(f1.path())
-        .arg(f2.path())
+        .arg(r#"_.filter_map(capture(r"id=(\d+)"))"#)
+        .write_stdin("GET /a id=17\nhealthcheck\nPOST /b id=204\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("5"));
+        .stdout("\"17\"\n\"204\"\n");
     Ok(())
 }
 
 #[test]
-fn parse_csv_stdin() -> Result<()> {
+fn is_empty() -> Result<()> {
     lob()
-        .arg("--parse-csv")
-        .arg("_.count()")
-        .write_stdin("name,age\nAlice,30\nBob,25\n")
+        .arg("_.filter(|x| x.starts_with(\"z\")).is_empty()")
+        .write_stdin("a\nb\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("2"));
+        .stdout(predicate::str::contains("true"));
     Ok(())
 }
 
 #[test]
-fn parse_csv_file() -> Result<()> {
-    let f = temp(
-        "csv",
-        "name,age,city\nAlice,30,NYC\nBob,25,LA\nCharlie,35,SF\n",
-    );
+fn is_empty_inside_closure_is_not_terminal() -> Result<()> {
     lob()
-        .arg("--parse-csv")
-        .arg("_.filter(|r| r[\"age\"].parse::<i32>().unwrap() > 26)")
-        .arg(f.path())
+        .arg("_.filter(|x| !x.is_empty())")
+        .write_stdin("a\n\nb\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Alice"))
-        .stdout(predicate::str::contains("Charlie"))
-        .stdout(predicate::str::contains("Bob").not());
+        .stdout("\"a\"\n\"b\"\n");
+    Ok(())
+}
+
+// ── Head / tail ──────────────────────────────────────────────────
+
+#[test]
+fn head_flag() -> Result<()> {
+    lob()
+        .args(["--head", "2", "_"])
+        .write_stdin("a\nb\nc\nd\n")
+        .assert()
+        .success()
+        .stdout("\"a\"\n\"b\"\n");
+    Ok(())
+}
+
+#[test]
+fn tail_flag() -> Result<()> {
+    lob()
+        .args(["--tail", "2", "_.map(|x| x.to_uppercase())"])
+        .write_stdin("a\nb\nc\nd\n")
+        .assert()
+        .success()
+        .stdout("\"C\"\n\"D\"\n");
+    Ok(())
+}
+
+#[test]
+fn head_and_tail_conflict() -> Result<()> {
+    lob()
+        .args(["--head", "1", "--tail", "1", "_"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    Ok(())
+}
+
+#[test]
+fn head_ignored_with_terminal() -> Result<()> {
+    lob()
+        .args(["--head", "1", "_.count()"])
+        .write_stdin("a\nb\nc\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3"))
+        .stderr(predicate::str::contains("--head/--tail ignored"));
+    Ok(())
+}
+
+#[test]
+fn input_limit_caps_records_read() -> Result<()> {
+    let big_input = "line\n".repeat(100_000);
+    lob()
+        .args(["--input-limit", "3", "_.count()"])
+        .write_stdin(big_input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3"));
+    Ok(())
+}
+
+#[test]
+fn input_limit_applies_before_expression() -> Result<()> {
+    // `--head` would keep the first two *outputs*; the input limit keeps the
+    // first two *inputs*, so only one of them survives the filter
+    lob()
+        .args(["--input-limit", "2", "_.filter(|x| x != \"a\")"])
+        .write_stdin("a\nb\nc\nd\n")
+        .assert()
+        .success()
+        .stdout("\"b\"\n");
+    Ok(())
+}
+
+#[test]
+fn distinct_flag() -> Result<()> {
+    lob()
+        .args(["--distinct", "_"])
+        .write_stdin("b\na\nb\nc\na\n")
+        .assert()
+        .success()
+        .stdout("\"b\"\n\"a\"\n\"c\"\n");
+    Ok(())
+}
+
+#[test]
+fn distinct_on_csv_rows_suggests_fix() -> Result<()> {
+    lob()
+        .args(["--distinct", "--parse-csv", "_"])
+        .write_stdin("name\nAlice\nAlice\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Eq + Hash + Clone"));
+    Ok(())
+}
+
+#[test]
+fn progress_reports_on_stderr_only() -> Result<()> {
+    lob()
+        .args(["--progress", "_.count()"])
+        .write_stdin("a\nb\nc\n")
+        .assert()
+        .success()
+        .stdout("3\n")
+        .stderr(predicate::str::contains("3 items ("));
+    Ok(())
+}
+
+#[test]
+fn seed_makes_sample_reproducible() -> Result<()> {
+    let input = (1..=100)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let run = |seed: &str| {
+        lob()
+            .args(["--seed", seed, "_.sample(3)"])
+            .write_stdin(input.clone())
+            .output()
+            .unwrap()
+    };
+
+    let first = run("42");
+    let second = run("42");
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+    assert_ne!(first.stdout, run("7").stdout);
+    Ok(())
+}
+
+#[test]
+fn null_value_cells_are_missing() -> Result<()> {
+    lob()
+        .args([
+            "--parse-csv",
+            "--null-value",
+            "NA",
+            "--null-value",
+            "",
+            "_.filter(|r| r.get(\"age\").is_none()).map(|r| r[\"name\"].clone())",
+        ])
+        .write_stdin("name,age\nAlice,NA\nBob,25\nCara,\n")
+        .assert()
+        .success()
+        .stdout("\"Alice\"\n\"Cara\"\n");
+    Ok(())
+}
+
+#[test]
+fn typed_csv_cells_become_json_values() -> Result<()> {
+    lob()
+        .args([
+            "--parse-csv",
+            "--typed",
+            "-f",
+            "jsonl",
+            "_.filter(|r| r[\"age\"].as_i64().unwrap() > 18).map(|r| (r[\"name\"].clone(), r[\"age\"].clone(), r[\"member\"].clone()))",
+        ])
+        .write_stdin("name,age,member\nAda,36,true\nBo,12,false\nCy,19,no\n")
+        .assert()
+        .success()
+        .stdout("[\"Ada\",36,true]\n[\"Cy\",19,\"no\"]\n");
+    Ok(())
+}
+
+#[test]
+fn typed_requires_parse_csv() -> Result<()> {
+    lob()
+        .args(["--typed", "_.count()"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--parse-csv"));
+    Ok(())
+}
+
+#[test]
+fn null_value_requires_csv_or_tsv() -> Result<()> {
+    lob()
+        .args(["--null-value", "NA", "_.count()"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--null-value requires"));
+    Ok(())
+}
+
+#[test]
+fn jobs_matches_sequential_output() -> Result<()> {
+    let input = (1..=500)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let expression =
+        "_.filter_map(|l| l.parse::<u64>().ok()).filter(|n| n % 3 != 0).map(|n| (n, n * n + 1))";
+
+    let sequential = lob()
+        .args(["-f", "jsonl", expression])
+        .write_stdin(input.clone())
+        .output()?;
+    let parallel = lob()
+        .args(["--jobs", "4", "-f", "jsonl", expression])
+        .write_stdin(input)
+        .output()?;
+
+    assert!(sequential.status.success() && parallel.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&sequential.stdout).lines().count(),
+        334
+    );
+    assert_eq!(parallel.stdout, sequential.stdout);
+    Ok(())
+}
+
+#[test]
+fn jobs_warns_for_non_parallel_expression() -> Result<()> {
+    lob()
+        .args(["--jobs", "2", "_.count()"])
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("--jobs ignored"));
+    Ok(())
+}
+
+#[test]
+fn resilient_skips_panicking_records() -> Result<()> {
+    lob()
+        .args([
+            "--resilient",
+            "-f",
+            "debug",
+            "_.map(|l| l.parse::<i32>().unwrap() * 2)",
+        ])
+        .write_stdin("1\n2\noops\n4\n")
+        .env_remove("RUST_BACKTRACE")
+        .assert()
+        .success()
+        .stdout("2\n4\n8\n")
+        .stderr(predicate::str::contains(
+            "Warning: skipped input record 3 after a panic",
+        ));
+    Ok(())
+}
+
+#[test]
+fn panic_aborts_without_resilient() -> Result<()> {
+    lob()
+        .args(["-f", "debug", "_.map(|l| l.parse::<i32>().unwrap() * 2)"])
+        .write_stdin("1\n2\noops\n4\n")
+        .env_remove("RUST_BACKTRACE")
+        .assert()
+        .failure()
+        .stdout("2\n4\n");
+    Ok(())
+}
+
+#[test]
+fn max_output_caps_records_and_notes_elision() -> Result<()> {
+    lob()
+        .args(["--max-output", "2", "-f", "debug", "_"])
+        .write_stdin("a\nb\nc\nd\ne\n")
+        .assert()
+        .success()
+        .stdout("\"a\"\n\"b\"\n")
+        .stderr(predicate::str::contains("... (more rows)"));
+    Ok(())
+}
+
+#[test]
+fn max_output_stops_reading_at_cap() -> Result<()> {
+    // An endless input must not keep the program counting past the cap
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("lob"))
+        .args(["--max-output", "2", "-f", "debug", "_"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || {
+        use std::io::Write;
+        while stdin.write_all(b"y\n").is_ok() {}
+    });
+
+    let output = child.wait_with_output()?;
+    writer.join().unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, "\"y\"\n\"y\"\n");
+    assert!(String::from_utf8(output.stderr)?.contains("... (more rows)"));
+    Ok(())
+}
+
+#[test]
+fn max_output_silent_when_under_cap() -> Result<()> {
+    lob()
+        .args(["--max-output", "5", "-f", "debug", "_"])
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stdout("\"a\"\n\"b\"\n")
+        .stderr(predicate::str::contains("more rows").not());
+    Ok(())
+}
+
+#[test]
+fn summary_counts_emitted_records() -> Result<()> {
+    lob()
+        .args([
+            "--summary",
+            "-f",
+            "jsonl",
+            "_.filter(|l| l.starts_with('a'))",
+        ])
+        .write_stdin("apple\nbanana\navocado\napricot\n")
+        .assert()
+        .success()
+        .stdout("\"apple\"\n\"avocado\"\n\"apricot\"\n")
+        .stderr(predicate::str::is_match(
+            r"summary: 3 records in \d+\.\d{3}s",
+        )?);
+    Ok(())
+}
+
+#[test]
+fn summary_counts_terminal_result_as_one_record() -> Result<()> {
+    lob()
+        .args(["--summary", "_.count()"])
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("summary: 1 record in"));
+    Ok(())
+}
+
+#[test]
+fn encoding_decodes_latin1_input() -> Result<()> {
+    let f = temp("log", "");
+    std::fs::write(f.path(), b"caf\xe9 cr\xe8me\nna\xefve\n")?;
+    lob()
+        .args(["--encoding", "latin1", "-f", "debug", "_"])
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout("\"café crème\"\n\"naïve\"\n");
+    Ok(())
+}
+
+#[test]
+fn encoding_rejects_unknown_label() -> Result<()> {
+    lob()
+        .args(["--encoding", "klingon", "_"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown encoding"));
+    Ok(())
+}
+
+#[test]
+fn strict_fails_on_malformed_json() -> Result<()> {
+    lob()
+        .args(["--parse-json", "--strict", "_.count()"])
+        .write_stdin("{\"a\": 1}\n{\"a\": oops}\n{\"a\": 3}\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("line 2: invalid JSON"))
+        .stderr(predicate::str::contains("{\"a\": oops}"));
+    Ok(())
+}
+
+#[test]
+fn malformed_json_is_skipped_without_strict() -> Result<()> {
+    lob()
+        .args(["--parse-json", "-f", "debug", "_.count()"])
+        .write_stdin("{\"a\": 1}\n{\"a\": oops}\n{\"a\": 3}\n")
+        .assert()
+        .success()
+        .stdout("2\n");
+    Ok(())
+}
+
+#[test]
+fn strict_rejects_encoded_input() -> Result<()> {
+    lob()
+        .args(["--encoding", "latin1", "--strict", "_.count()"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--strict supports"));
+    Ok(())
+}
+
+#[test]
+fn strict_rejects_log_input() -> Result<()> {
+    lob()
+        .args(["--parse-logs", "--strict", "_.count()"])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--strict supports"));
+    Ok(())
+}
+
+// ── Input formats ────────────────────────────────────────────────
+
+#[test]
+fn stdin_input() -> Result<()> {
+    lob()
+        .arg("_.count()")
+        .write_stdin("hello\nworld\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2"));
+    Ok(())
+}
+
+#[test]
+fn file_input() -> Result<()> {
+    let f = temp("txt", "hello\nworld\ntest\n");
+    lob()
+        .arg("_.filter(|x| x.len() > 4)")
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"hello\""))
+        .stdout(predicate::str::contains("\"world\""));
+    Ok(())
+}
+
+#[test]
+fn multi_file_input() -> Result<()> {
+    let f1 = temp("txt", "a\nb\nc\n");
+    let f2 = temp("txt", "c\nd\ne\n");
+    lob()
+        .arg("_.unique().count()")
+        .arg(f1.path())
+        .arg(f2.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("5"));
+    Ok(())
+}
+
+#[test]
+fn parse_csv_stdin() -> Result<()> {
+    lob()
+        .arg("--parse-csv")
+        .arg("_.count()")
+        .write_stdin("name,age\nAlice,30\nBob,25\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2"));
+    Ok(())
+}
+
+#[test]
+fn parse_csv_file() -> Result<()> {
+    let f = temp(
+        "csv",
+        "name,age,city\nAlice,30,NYC\nBob,25,LA\nCharlie,35,SF\n",
+    );
+    lob()
+        .arg("--parse-csv")
+        .arg("_.filter(|r| r[\"age\"].parse::<i32>().unwrap() > 26)")
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Alice"))
+        .stdout(predicate::str::contains("Charlie"))
+        .stdout(predicate::str::contains("Bob").not());
+    Ok(())
+}
+
+#[test]
+fn parse_tsv() -> Result<()> {
+    lob()
+        .arg("--parse-tsv")
+        .arg("_.count()")
+        .write_stdin("name\tage\nAlice\t30\nBob\t25\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2"));
+    Ok(())
+}
+
+#[test]
+fn parse_json() -> Result<()> {
+    lob()
+        .arg("--parse-json")
+        .arg("_.count()")
+        .write_stdin("{\"a\":1}\n{\"b\":2}\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2"));
+    Ok(())
+}
+
+#[test]
+fn parse_json_array() -> Result<()> {
+    lob()
+        .arg("--parse-json-array")
+        .arg("_.count()")
+        .write_stdin("[{\"a\":1},{\"a\":2}]")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2"));
+    Ok(())
+}
+
+#[test]
+fn parse_json_array_file() -> Result<()> {
+    let f = temp("json", "[\n  {\"a\": 1},\n  {\"a\": 2},\n  {\"a\": 3}\n]\n");
+    lob()
+        .arg("--parse-json-array")
+        .arg("_.map(|v| v[\"a\"].as_i64().unwrap()).sum::<i64>()")
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("6"));
+    Ok(())
+}
+
+#[test]
+fn parse_logs_count_by_status() -> Result<()> {
+    let logs = concat!(
+        r#"10.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.1" 200 512 "-" "curl/8.0""#,
+        "\n",
+        r#"10.0.0.2 - - [10/Oct/2000:13:55:37 -0700] "GET /missing HTTP/1.1" 404 0 "-" "curl/8.0""#,
+        "\n",
+        "garbage line\n",
+        r#"10.0.0.3 - - [10/Oct/2000:13:55:38 -0700] "POST /api HTTP/1.1" 200 64 "-" "curl/8.0""#,
+        "\n",
+    );
+    lob()
+        .arg("--parse-logs")
+        .arg(r#"_.group_by_agg(|r| r["status"].clone(), || 0, |n, _| n + 1)"#)
+        .write_stdin(logs)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"200\":2"))
+        .stdout(predicate::str::contains("\"404\":1"));
+    Ok(())
+}
+
+#[test]
+fn fields_fixed_width() -> Result<()> {
+    lob()
+        .args(["--fields", "1-5,7-10", "_"])
+        .write_stdin("ALICE 0030 NYC\nBOB   0025\nEVE\n")
+        .assert()
+        .success()
+        .stdout("[\"ALICE\",\"0030\"]\n[\"BOB\",\"0025\"]\n[\"EVE\",\"\"]\n");
+    Ok(())
+}
+
+#[test]
+fn fields_invalid_spec() -> Result<()> {
+    lob()
+        .args(["--fields", "5-1", "_"])
+        .write_stdin("abc\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid field range"));
+    Ok(())
+}
+
+#[test]
+fn parse_json_path_nested_field() -> Result<()> {
+    let ndjson = concat!(
+        r#"{"user": {"address": {"city": "Oslo"}}}"#,
+        "\n",
+        r#"{"user": {"name": "no address"}}"#,
+        "\n",
+        r#"{"user": {"address": {"city": "Lima"}}}"#,
+        "\n",
+    );
+    lob()
+        .args([
+            "--parse-json",
+            "--parse-json-path",
+            "user.address.city",
+            "_.count()",
+        ])
+        .write_stdin(ndjson)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2"));
+
+    lob()
+        .args([
+            "--parse-json",
+            "--parse-json-path",
+            "user.address.city",
+            "_",
+        ])
+        .write_stdin(ndjson)
+        .assert()
+        .success()
+        .stdout("\"Oslo\"\n\"Lima\"\n");
+    Ok(())
+}
+
+#[test]
+fn parse_json_path_requires_json_input() -> Result<()> {
+    lob()
+        .args(["--parse-json-path", "a.b", "_"])
+        .write_stdin("x\n")
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[test]
+fn select_columns() -> Result<()> {
+    let f = temp("csv", "name,age,city\nAlice,30,NYC\nBob,25,LA\n");
+    lob()
+        .arg("--parse-csv")
+        .arg("--select")
+        .arg("name,city")
+        .arg("_.take(2)")
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"name\":\"Alice\""))
+        .stdout(predicate::str::contains("\"city\":\"LA\""))
+        .stdout(predicate::str::contains("age").not());
+    Ok(())
+}
+
+#[test]
+fn select_requires_header_input() -> Result<()> {
+    lob()
+        .arg("--select")
+        .arg("name")
+        .arg("_.count()")
+        .write_stdin("a\n")
+        .assert()
+        .failure();
+    Ok(())
+}
+
+// ── Output formats ───────────────────────────────────────────────
+
+#[test]
+fn output_default_jsonl_when_piped() -> Result<()> {
+    // When piped (not a terminal) default format is jsonl
+    lob()
+        .arg("_.take(2)")
+        .write_stdin("a\nb\nc\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"a\""))
+        .stdout(predicate::str::contains("\"b\""))
+        .stdout(predicate::str::contains("\"c\"").not());
+    Ok(())
+}
+
+#[test]
+fn output_json() -> Result<()> {
+    let f = temp("txt", "1\n2\n3\n");
+    lob()
+        .arg("--format")
+        .arg("json")
+        .arg("_.take(2)")
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("["))
+        .stdout(predicate::str::contains("]"))
+        .stdout(predicate::str::contains("\"1\""))
+        .stdout(predicate::str::contains("\"2\""));
+    Ok(())
+}
+
+#[test]
+fn output_jsonl() -> Result<()> {
+    lob()
+        .arg("--format")
+        .arg("jsonl")
+        .arg("_.take(2)")
+        .write_stdin("1\n2\n3\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"1\""))
+        .stdout(predicate::str::contains("\"2\""))
+        .stdout(predicate::str::contains("\"3\"").not());
+    Ok(())
+}
+
+#[test]
+fn output_csv() -> Result<()> {
+    lob()
+        .arg("--format")
+        .arg("csv")
+        .arg("_.take(2)")
+        .write_stdin("hello\nworld\nfoo\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello"))
+        .stdout(predicate::str::contains("world"))
+        .stdout(predicate::str::contains("foo").not());
+    Ok(())
+}
+
+#[test]
+fn output_table() -> Result<()> {
+    let f = temp("csv", "name,age\nAlice,30\nBob,25\n");
+    lob()
+        .arg("--parse-csv")
+        .arg("--format")
+        .arg("table")
+        .arg("_.take(2)")
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Alice"))
+        .stdout(predicate::str::contains("Bob"));
+    Ok(())
+}
+
+#[test]
+fn output_csv_preserves_source_column_order() -> Result<()> {
+    let f = temp("csv", "name,age,city\nAlice,30,NYC\nBob,25,LA\n");
+    lob()
+        .args(["--parse-csv", "--format", "csv", "_"])
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout("name,age,city\nAlice,30,NYC\nBob,25,LA\n");
+    Ok(())
+}
+
+#[test]
+fn output_csv_keeps_columns_missing_from_first_row() -> Result<()> {
+    let f = temp("csv", "name,age,city\nA,NA,X\nB,3,Y\n");
+    lob()
+        .args(["--parse-csv", "--null-value", "NA", "--format", "csv", "_"])
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout("name,age,city\nA,,X\nB,3,Y\n");
+    Ok(())
+}
+
+#[test]
+fn output_csv_unions_headers_across_files() -> Result<()> {
+    let a = temp("csv", "name,age\nA,1\n");
+    let b = temp("csv", "name,city\nB,Y\n");
+    lob()
+        .args(["--parse-csv", "--format", "csv", "_"])
+        .arg(a.path())
+        .arg(b.path())
+        .assert()
+        .success()
+        .stdout("name,age,city\nA,1,\nB,,Y\n")
+        .stderr(predicate::str::contains(
+            "input header (name, city) differs from the first input's (name, age)",
+        ));
+    Ok(())
+}
+
+#[test]
+fn output_csv_appends_later_file_columns_in_their_order() -> Result<()> {
+    let a = temp("csv", "id\n1\n");
+    let b = temp("csv", "id,zeta,alpha\n2,z,a\n");
+    lob()
+        .args(["--parse-csv", "--format", "csv", "_"])
+        .arg(a.path())
+        .arg(b.path())
+        .assert()
+        .success()
+        .stdout("id,zeta,alpha\n1,,\n2,z,a\n");
+    Ok(())
+}
+
+#[test]
+fn output_table_keeps_columns_missing_from_first_row() -> Result<()> {
+    let f = temp("csv", "name,age\nA,NA\nB,3\n");
+    lob()
+        .args([
+            "--parse-csv",
+            "--null-value",
+            "NA",
+            "--format",
+            "table",
+            "--table-style",
+            "ascii",
+            "_",
+        ])
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| name | age |"));
+    Ok(())
+}
+
+#[test]
+fn output_table_preserves_source_column_order() -> Result<()> {
+    let f = temp("csv", "name,age,city\nAlice,30,NYC\n");
+    lob()
+        .args([
+            "--parse-csv",
+            "--format",
+            "table",
+            "--table-style",
+            "ascii",
+            "_",
+        ])
+        .arg(f.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| name  | age | city |"));
     Ok(())
 }
 
 #[test]
-fn parse_tsv() -> Result<()> {
+fn output_csv_json_rows_sorted() -> Result<()> {
     lob()
-        .arg("--parse-tsv")
-        .arg("_.count()")
-        .write_stdin("name\tage\nAlice\t30\nBob\t25\n")
+        .args(["--parse-json", "--format", "csv", "_"])
+        .write_stdin("{\"b\": 1, \"a\": \"x\"}\n")
         .assert()
         .success()
-        .stdout(predicate::str::contains("2"));
+        .stdout("a,b\nx,1\n");
     Ok(())
 }
 
 #[test]
-fn parse_json() -> Result<()> {
+fn output_table_ascii_style() -> Result<()> {
+    let f = temp("csv", "name,age\nAlice,30\n");
     lob()
-        .arg("--parse-json")
-        .arg("_.count()")
-        .write_stdin("{\"a\":1}\n{\"b\":2}\n")
+        .args([
+            "--parse-csv",
+            "--format",
+            "table",
+            "--table-style",
+            "ascii",
+            "_",
+        ])
+        .arg(f.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains("2"));
+        .stdout(predicate::str::contains("+-------+-----+"))
+        .stdout(predicate::str::contains("| Alice |"))
+        .stdout(predicate::str::contains("╭").not());
     Ok(())
 }
 
-// ── Output formats ───────────────────────────────────────────────
-
 #[test]
-fn output_default_jsonl_when_piped() -> Result<()> {
-    // When piped (not a terminal) default format is jsonl
+fn output_table_markdown_style() -> Result<()> {
+    let f = temp("csv", "name\nAlice\n");
     lob()
-        .arg("_.take(2)")
-        .write_stdin("a\nb\nc\n")
+        .args([
+            "--parse-csv",
+            "--format",
+            "table",
+            "--table-style",
+            "markdown",
+            "_",
+        ])
+        .arg(f.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"a\""))
-        .stdout(predicate::str::contains("\"b\""))
-        .stdout(predicate::str::contains("\"c\"").not());
+        .stdout(predicate::str::contains("|-------|"));
     Ok(())
 }
 
 #[test]
-fn output_json() -> Result<()> {
-    let f = temp("txt", "1\n2\n3\n");
+fn output_toml_single_record() -> Result<()> {
+    let f = temp("csv", "name,city\nAlice,NYC\n");
     lob()
+        .arg("--parse-csv")
         .arg("--format")
-        .arg("json")
-        .arg("_.take(2)")
+        .arg("toml")
+        .arg("_.first().unwrap()")
         .arg(f.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains("["))
-        .stdout(predicate::str::contains("]"))
-        .stdout(predicate::str::contains("\"1\""))
-        .stdout(predicate::str::contains("\"2\""));
+        .stdout(predicate::str::contains("name = \"Alice\""))
+        .stdout(predicate::str::contains("city = \"NYC\""));
     Ok(())
 }
 
 #[test]
-fn output_jsonl() -> Result<()> {
+fn output_toml_array_of_tables() -> Result<()> {
+    let f = temp("csv", "name\nAlice\nBob\n");
     lob()
+        .arg("--parse-csv")
         .arg("--format")
-        .arg("jsonl")
-        .arg("_.take(2)")
-        .write_stdin("1\n2\n3\n")
+        .arg("toml")
+        .arg("_")
+        .arg(f.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"1\""))
-        .stdout(predicate::str::contains("\"2\""))
-        .stdout(predicate::str::contains("\"3\"").not());
+        .stdout(predicate::str::contains("[[items]]\nname = \"Alice\""))
+        .stdout(predicate::str::contains("name = \"Bob\""));
     Ok(())
 }
 
 #[test]
-fn output_csv() -> Result<()> {
+fn output_toml_rejects_scalar() -> Result<()> {
     lob()
         .arg("--format")
-        .arg("csv")
-        .arg("_.take(2)")
-        .write_stdin("hello\nworld\nfoo\n")
+        .arg("toml")
+        .arg("_.count()")
+        .write_stdin("a\n")
         .assert()
-        .success()
-        .stdout(predicate::str::contains("hello"))
-        .stdout(predicate::str::contains("world"))
-        .stdout(predicate::str::contains("foo").not());
+        .failure()
+        .stderr(predicate::str::contains("cannot be written as TOML"));
     Ok(())
 }
 
 #[test]
-fn output_table() -> Result<()> {
-    let f = temp("csv", "name,age\nAlice,30\nBob,25\n");
+fn output_yaml_sequence_from_csv() -> Result<()> {
+    let f = temp("csv", "name,city\nAlice,NYC\nBob,LA\n");
     lob()
-        .arg("--parse-csv")
-        .arg("--format")
-        .arg("table")
-        .arg("_.take(2)")
+        .args(["--parse-csv", "--format", "yaml", "_"])
         .arg(f.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains("Alice"))
-        .stdout(predicate::str::contains("Bob"));
+        .stdout(predicate::str::is_match(
+            r"(?m)^- (name: Alice|city: NYC)$",
+        )?)
+        .stdout(predicate::str::is_match(r"(?m)^- (name: Bob|city: LA)$")?);
+    Ok(())
+}
+
+#[test]
+fn output_yaml_scalar_document() -> Result<()> {
+    lob()
+        .args(["--format", "yaml", "_.count()"])
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stdout("2\n");
     Ok(())
 }
 
@@ -542,6 +1549,66 @@ fn show_source() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn show_source_explain() -> Result<()> {
+    lob()
+        .args(["--show-source", "--explain", "_.count()"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "// Input: input() yields plain text lines",
+        ))
+        .stdout(predicate::str::contains("// Terminal: detected"));
+    Ok(())
+}
+
+#[test]
+fn explain_requires_show_source() -> Result<()> {
+    lob()
+        .args(["--explain", "_.count()"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--show-source"));
+    Ok(())
+}
+
+#[test]
+fn show_source_format_rust() -> Result<()> {
+    let rustfmt_available = std::process::Command::new("rustfmt")
+        .arg("--version")
+        .output()
+        .is_ok_and(|o| o.status.success());
+    if !rustfmt_available {
+        return Ok(());
+    }
+    let expr = r#"_.filter(|l| l.len() > 3).map(|l| l.to_uppercase()).map(|l| l.replace("A", "B")).take(10)"#;
+    let raw = lob().args(["--show-source", expr]).output()?;
+    let formatted = lob()
+        .args(["--show-source", "--format", "rust", expr])
+        .output()?;
+    assert!(formatted.status.success());
+    let raw = String::from_utf8(raw.stdout)?;
+    let formatted = String::from_utf8(formatted.stdout)?;
+    assert_ne!(raw, formatted);
+    // The long chain is wrapped one call per line
+    assert!(formatted.contains("\n        .map(|l| l.to_uppercase())\n"));
+    Ok(())
+}
+
+#[test]
+fn format_rust_requires_show_source() -> Result<()> {
+    lob()
+        .args(["--format", "rust", "_.count()"])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format rust only applies to --show-source",
+        ));
+    Ok(())
+}
+
 #[test]
 fn show_source_csv() -> Result<()> {
     lob()
@@ -614,6 +1681,23 @@ fn version_flag() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn pre_statements() -> Result<()> {
+    lob()
+        .args([
+            "--pre",
+            "let threshold = 3;",
+            "--pre",
+            "let suffix = \"!\";",
+        ])
+        .arg("_.filter(|x| x.len() > threshold).map(|x| format!(\"{}{}\", x, suffix))")
+        .write_stdin("ab\nabcd\nabcdef\n")
+        .assert()
+        .success()
+        .stdout("\"abcd!\"\n\"abcdef!\"\n");
+    Ok(())
+}
+
 // ── Error handling ───────────────────────────────────────────────
 
 #[test]
@@ -707,6 +1791,55 @@ fn error_method_not_found() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn error_format_json_is_parseable() -> Result<()> {
+    let output = lob()
+        .args(["--error-format", "json", "_.filter(|x| x.len())"])
+        .write_stdin("a\nb\n")
+        .output()?;
+    assert!(!output.status.success());
+    // A first run may note the toolchain extraction before the report
+    let stderr = String::from_utf8(output.stderr)?;
+    let report: serde_json::Value = serde_json::from_str(stderr.lines().last().unwrap())?;
+    assert!(report["problem"].is_null() || report["problem"].is_string());
+    assert!(report["fixes"].is_array());
+    assert!(report["rustc"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|line| line.as_str().unwrap().starts_with("error[E0308]")));
+    Ok(())
+}
+
+#[test]
+fn exec_timeout_stops_a_stuck_program() -> Result<()> {
+    let start = std::time::Instant::now();
+    lob()
+        .args([
+            "--exec-timeout",
+            "1",
+            "_.map(|l| { std::thread::sleep(std::time::Duration::from_secs(60)); l })",
+        ])
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Execution timed out after 1s"));
+    // Compilation is not covered by the timeout, so allow it some headroom
+    assert!(start.elapsed() < std::time::Duration::from_secs(45));
+    Ok(())
+}
+
+#[test]
+fn exec_timeout_allows_fast_programs() -> Result<()> {
+    lob()
+        .args(["--exec-timeout", "30", "_.count()"])
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stdout("2\n");
+    Ok(())
+}
+
 // ── Caching ──────────────────────────────────────────────────────
 
 #[test]
@@ -773,6 +1906,46 @@ fn range_expression() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn jsonl_pretty() -> Result<()> {
+    lob()
+        .args(["--parse-csv", "--format", "jsonl", "--pretty", "_"])
+        .write_stdin("name\nAlice\nBob\n")
+        .assert()
+        .success()
+        .stdout("{\n  \"name\": \"Alice\"\n}\n{\n  \"name\": \"Bob\"\n}\n");
+    Ok(())
+}
+
+#[test]
+fn pretty_ignored_for_csv_output() -> Result<()> {
+    lob()
+        .args(["--parse-csv", "--format", "csv", "--pretty", "_"])
+        .write_stdin("name\nAlice\n")
+        .assert()
+        .success()
+        .stdout("name\nAlice\n")
+        .stderr(predicate::str::contains("--pretty ignored"));
+    Ok(())
+}
+
+#[test]
+fn no_trailing_newline() -> Result<()> {
+    lob()
+        .args(["--no-trailing-newline", "_.map(|x| x.len())"])
+        .write_stdin("a\nbb\nccc\n")
+        .assert()
+        .success()
+        .stdout("1\n2\n3");
+    lob()
+        .args(["--no-trailing-newline", "--format", "debug", "_.count()"])
+        .write_stdin("a\nbb\n")
+        .assert()
+        .success()
+        .stdout("2");
+    Ok(())
+}
+
 // ── CSV with different output formats ────────────────────────────
 
 #[test]
@@ -791,3 +1964,273 @@ fn csv_with_json_output() -> Result<()> {
         .stdout(predicate::str::contains("Bob"));
     Ok(())
 }
+
+// ── Expression file ──────────────────────────────────────────────
+
+#[test]
+fn expr_file() -> Result<()> {
+    let expr = temp("rs", "_.filter(|x| x.len() > 1)\n   .count()\n");
+    lob()
+        .arg("--expr-file")
+        .arg(expr.path())
+        .write_stdin("a\nbb\nccc\n")
+        .assert()
+        .success()
+        .stdout("2\n");
+    Ok(())
+}
+
+#[test]
+fn expr_file_with_input_file() -> Result<()> {
+    let expr = temp("rs", "_.count()\n");
+    let data = temp("txt", "a\nb\nc\n");
+    lob()
+        .arg("--expr-file")
+        .arg(expr.path())
+        .arg(data.path())
+        .assert()
+        .success()
+        .stdout("3\n");
+    Ok(())
+}
+
+#[test]
+fn expr_file_missing() -> Result<()> {
+    lob()
+        .args(["--expr-file", "/nonexistent/pipeline.rs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot read expression file"));
+    Ok(())
+}
+
+// ── Watch mode ───────────────────────────────────────────────────
+
+#[test]
+fn watch_reruns_on_file_change() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    let f = temp("txt", "a\nb\n");
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("lob"))
+        .arg("--watch")
+        .arg("_.count()")
+        .arg(f.path())
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+
+    let (tx, rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout)
+            .lines()
+            .map_while(std::io::Result::ok)
+        {
+            let _ = tx.send(line);
+        }
+    });
+
+    let first = rx.recv_timeout(Duration::from_mins(2));
+
+    // The watcher is only set up after the first run, so keep touching the
+    // file until a second execution shows up
+    let deadline = Instant::now() + Duration::from_secs(20);
+    let mut second = None;
+    while second.is_none() && Instant::now() < deadline {
+        fs::OpenOptions::new()
+            .append(true)
+            .open(f.path())?
+            .write_all(b"c\n")?;
+        second = rx.recv_timeout(Duration::from_millis(500)).ok();
+    }
+
+    child.kill()?;
+    child.wait()?;
+
+    assert_eq!(first?, "2");
+    let second = second.expect("no second execution after modifying the input file");
+    assert!(second.parse::<usize>()? > 2);
+    Ok(())
+}
+
+#[test]
+fn watch_requires_files() -> Result<()> {
+    lob()
+        .arg("--watch")
+        .arg("_.count()")
+        .write_stdin("a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot watch stdin"));
+    Ok(())
+}
+
+// ── REPL ─────────────────────────────────────────────────────────
+
+#[test]
+fn repl_runs_each_expression() -> Result<()> {
+    let f = temp("txt", "a\nbb\nccc\n");
+    lob()
+        .arg("--repl")
+        .arg(f.path())
+        .write_stdin("_.count()\n_.filter(|x| x.len() > 2)\n:quit\n_.count()\n")
+        .assert()
+        .success()
+        .stdout("3\n\"ccc\"\n");
+    Ok(())
+}
+
+#[test]
+fn repl_source_shows_last_generated_code() -> Result<()> {
+    let f = temp("txt", "a\n");
+    lob()
+        .arg("--repl")
+        .arg(f.path())
+        .write_stdin("_.count()\n:source\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fn main()"))
+        .stdout(predicate::str::contains("stdin_data.count()"));
+    Ok(())
+}
+
+#[test]
+fn repl_keeps_going_after_compile_error() -> Result<()> {
+    let f = temp("txt", "a\nb\n");
+    lob()
+        .arg("--repl")
+        .arg(f.path())
+        .write_stdin("_.not_a_method()\n_.count()\n")
+        .assert()
+        .success()
+        .stdout("2\n")
+        .stderr(predicate::str::contains("Compilation Error"));
+    Ok(())
+}
+
+#[test]
+fn repl_applies_exec_timeout() -> Result<()> {
+    let f = temp("txt", "a\nb\n");
+    lob()
+        .args(["--repl", "--exec-timeout", "1"])
+        .arg(f.path())
+        .write_stdin(
+            "_.map(|l| { std::thread::sleep(std::time::Duration::from_secs(60)); l })\n_.count()\n",
+        )
+        .assert()
+        .success()
+        .stdout("2\n")
+        .stderr(predicate::str::contains("Execution timed out after 1s"));
+    Ok(())
+}
+
+// ── Daemon ───────────────────────────────────────────────────────
+
+/// A `lob --daemon` process with its own cache directory, killed on drop
+#[cfg(unix)]
+struct Daemon {
+    child: std::process::Child,
+    cache_dir: PathBuf,
+}
+
+#[cfg(unix)]
+impl Daemon {
+    fn start() -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let cache_dir =
+            std::env::temp_dir().join(format!("lob_daemon_{}_{}", std::process::id(), id));
+        let child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("lob"))
+            .arg("--daemon")
+            .env("LOB_CACHE_DIR", &cache_dir)
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        while !cache_dir.join("daemon.sock").exists() {
+            assert!(std::time::Instant::now() < deadline, "daemon never started");
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        Self { child, cache_dir }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.cache_dir);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn daemon_compiles_requests_over_socket() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let daemon = Daemon::start();
+    let mut stream = UnixStream::connect(daemon.cache_dir.join("daemon.sock"))?;
+    for word in ["one", "two"] {
+        let request = serde_json::json!({
+            "source": format!("fn main() {{ println!(\"{}\"); }}", word),
+            "expression": null,
+        });
+        writeln!(stream, "{}", request)?;
+    }
+
+    let mut paths = Vec::new();
+    for line in BufReader::new(&stream).lines().take(2) {
+        let response: serde_json::Value = serde_json::from_str(&line?)?;
+        assert_eq!(response["status"], "compiled");
+        paths.push(PathBuf::from(response["binary_path"].as_str().unwrap()));
+    }
+
+    assert_ne!(paths[0], paths[1]);
+    let output = std::process::Command::new(&paths[1]).output()?;
+    assert_eq!(String::from_utf8(output.stdout)?, "two\n");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn daemon_env_routes_compiles_through_daemon() -> Result<()> {
+    let daemon = Daemon::start();
+    lob()
+        .env("LOB_CACHE_DIR", &daemon.cache_dir)
+        .env("LOB_DAEMON", "1")
+        .arg("-v")
+        .arg("_.count()")
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stdout("2\n")
+        .stderr(predicate::str::contains("Compiling via daemon"));
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn daemon_idle_connection_does_not_block_others() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let daemon = Daemon::start();
+    let _idle = UnixStream::connect(daemon.cache_dir.join("daemon.sock"))?;
+    let mut stream = UnixStream::connect(daemon.cache_dir.join("daemon.sock"))?;
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(90)))?;
+    let request = serde_json::json!({
+        "source": "fn main() { println!(\"busy\"); }",
+        "expression": null,
+    });
+    writeln!(stream, "{}", request)?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(&line)?;
+    assert_eq!(response["status"], "compiled");
+    Ok(())
+}
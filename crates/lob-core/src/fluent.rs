@@ -1,8 +1,19 @@
 //! Core Lob wrapper type and fluent API
 
-use crate::grouping::{ChunkIterator, GroupByCollectIterator, WindowIterator};
+use crate::grouping::{
+    BatchBySizeIterator, ChunkIterator, ChunkOnChangeIterator, ChunkWhileIterator,
+    GroupByBoundedIterator, GroupByCollectIterator, GroupByOrderedIterator, RunLengthIterator,
+    SplitOnIterator, WindowIterator, WindowStepIterator,
+};
 use crate::joins::{InnerJoinIterator, LeftJoinIterator};
-use std::collections::HashSet;
+use crate::numeric::{DiffIterator, RollingMeanIterator};
+use crate::random::{SplitMix64, WeightedItem};
+use crate::selection::{DedupByIterator, DedupByKeyIterator, DedupIterator, SkipLastIterator};
+use crate::transformation::{InterleaveIterator, IntersperseIterator, MergeIterator};
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
 /// Main wrapper type for fluent iterator operations
@@ -99,6 +110,57 @@ impl<I: Iterator> Lob<I> {
         Lob::new(self.iter.skip(n))
     }
 
+    /// Skip the last n elements
+    ///
+    /// Elements are emitted n positions behind the input, so at most n + 1
+    /// elements are buffered. Useful for trimming trailers and footers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (0..5)
+    ///     .lob()
+    ///     .skip_last(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![0, 1, 2]);
+    /// ```
+    #[must_use]
+    pub fn skip_last(self, n: usize) -> Lob<impl Iterator<Item = I::Item>> {
+        Lob::new(SkipLastIterator::new(self.iter, n))
+    }
+
+    /// Drop every n-th element, counting from 1, and keep the rest
+    ///
+    /// With `n = 3` the 3rd, 6th, 9th, ... elements are dropped; `n = 1` drops
+    /// everything.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (1..=7).lob().skip_every(3).collect();
+    ///
+    /// assert_eq!(result, vec![1, 2, 4, 5, 7]);
+    /// ```
+    #[must_use]
+    pub fn skip_every(self, n: usize) -> Lob<impl Iterator<Item = I::Item>> {
+        assert!(n > 0, "skip_every n must be greater than 0");
+        Lob::new(
+            self.iter
+                .enumerate()
+                .filter(move |(i, _)| (i + 1) % n != 0)
+                .map(|(_, item)| item),
+        )
+    }
+
     /// Take elements while predicate is true
     ///
     /// # Examples
@@ -169,373 +231,2153 @@ impl<I: Iterator> Lob<I> {
         Lob::new(self.iter.filter(move |item| seen.insert(item.clone())))
     }
 
-    // ========== Transformation Operations (lazy) ==========
-
-    /// Transform each element
+    /// Collapse consecutive duplicate elements (like Unix `uniq`)
+    ///
+    /// Unlike `unique`, only adjacent duplicates are removed and nothing
+    /// beyond the last emitted element is remembered.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = vec![1, 2, 3]
+    /// let result: Vec<_> = vec![1, 1, 2, 1, 1]
     ///     .into_iter()
     ///     .lob()
-    ///     .map(|x| x * 2)
+    ///     .dedup()
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![2, 4, 6]);
+    /// assert_eq!(result, vec![1, 2, 1]);
     /// ```
     #[must_use]
-    pub fn map<F, B>(self, f: F) -> Lob<impl Iterator<Item = B>>
+    pub fn dedup(self) -> Lob<impl Iterator<Item = I::Item>>
     where
-        F: FnMut(I::Item) -> B,
+        I::Item: PartialEq + Clone,
     {
-        Lob::new(self.iter.map(f))
+        Lob::new(DedupIterator::new(self.iter))
     }
 
-    /// Add index to each element
+    /// Collapse consecutive elements that `same` considers equal
+    ///
+    /// `same(kept, next)` compares the first element of the current run with
+    /// each following element; the run continues while it returns `true`.
+    /// This generalizes `dedup` to any equivalence, such as case-insensitive
+    /// comparison.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = vec!["a", "b", "c"]
+    /// let result: Vec<_> = vec!["Error", "ERROR", "error", "warn", "Error"]
     ///     .into_iter()
     ///     .lob()
-    ///     .enumerate()
+    ///     .dedup_by(|a, b| a.eq_ignore_ascii_case(b))
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![(0, "a"), (1, "b"), (2, "c")]);
+    /// assert_eq!(result, vec!["Error", "warn", "Error"]);
     /// ```
     #[must_use]
-    pub fn enumerate(self) -> Lob<impl Iterator<Item = (usize, I::Item)>> {
-        Lob::new(self.iter.enumerate())
+    pub fn dedup_by<F>(self, same: F) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Clone,
+        F: FnMut(&I::Item, &I::Item) -> bool,
+    {
+        Lob::new(DedupByIterator::new(self.iter, same))
     }
 
-    /// Zip with another iterator
+    /// Collapse consecutive elements that share the same derived key
+    ///
+    /// The first element of each run is kept. Equal keys that are not
+    /// adjacent are preserved.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = vec![1, 2, 3]
+    /// let result: Vec<_> = vec!["10:00 a", "10:00 b", "10:01 c", "10:00 d"]
     ///     .into_iter()
     ///     .lob()
-    ///     .zip(vec!["a", "b", "c"])
+    ///     .dedup_by_key(|line| line[..5].to_string())
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![(1, "a"), (2, "b"), (3, "c")]);
+    /// assert_eq!(result, vec!["10:00 a", "10:01 c", "10:00 d"]);
     /// ```
     #[must_use]
-    pub fn zip<J>(self, other: J) -> Lob<impl Iterator<Item = (I::Item, J::Item)>>
+    pub fn dedup_by_key<K, F>(self, key_fn: F) -> Lob<impl Iterator<Item = I::Item>>
     where
-        J: IntoIterator,
+        K: PartialEq,
+        F: FnMut(&I::Item) -> K,
     {
-        Lob::new(self.iter.zip(other))
+        Lob::new(DedupByKeyIterator::new(self.iter, key_fn))
     }
 
-    /// Flatten nested iterators
+    // ========== Transformation Operations (lazy) ==========
+
+    /// Transform each element
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = vec![vec![1, 2], vec![3, 4]]
+    /// let result: Vec<_> = vec![1, 2, 3]
     ///     .into_iter()
     ///     .lob()
-    ///     .flatten()
+    ///     .map(|x| x * 2)
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![1, 2, 3, 4]);
+    /// assert_eq!(result, vec![2, 4, 6]);
     /// ```
     #[must_use]
-    pub fn flatten<T>(self) -> Lob<impl Iterator<Item = T>>
+    pub fn map<F, B>(self, f: F) -> Lob<impl Iterator<Item = B>>
     where
-        I::Item: IntoIterator<Item = T>,
+        F: FnMut(I::Item) -> B,
     {
-        Lob::new(self.iter.flatten())
+        Lob::new(self.iter.map(f))
     }
 
-    // ========== Grouping Operations ==========
-
-    /// Group elements into chunks of size n
+    /// Transform each element, keeping only the `Some` results
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = (0..5)
+    /// let result: Vec<_> = vec!["1", "x", "3"]
+    ///     .into_iter()
     ///     .lob()
-    ///     .chunk(2)
+    ///     .filter_map(|s| s.parse::<i32>().ok())
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![vec![0, 1], vec![2, 3], vec![4]]);
+    /// assert_eq!(result, vec![1, 3]);
     /// ```
     #[must_use]
-    pub fn chunk(self, n: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>> {
-        Lob::new(ChunkIterator::new(self.iter, n))
+    pub fn filter_map<F, B>(self, f: F) -> Lob<impl Iterator<Item = B>>
+    where
+        F: FnMut(I::Item) -> Option<B>,
+    {
+        Lob::new(self.iter.filter_map(f))
     }
 
-    /// Create sliding windows of size n
+    /// Transform elements until `f` first returns `None`
+    ///
+    /// Like `take_while` combined with `map`: the stream ends at the first `None`.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = (1..=4)
+    /// let result: Vec<_> = vec!["1", "2", "end", "3"]
+    ///     .into_iter()
     ///     .lob()
-    ///     .window(2)
+    ///     .map_while(|s| s.parse::<i32>().ok())
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![vec![1, 2], vec![2, 3], vec![3, 4]]);
+    /// assert_eq!(result, vec![1, 2]);
     /// ```
     #[must_use]
-    pub fn window(self, n: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    pub fn map_while<F, B>(self, f: F) -> Lob<impl Iterator<Item = B>>
     where
-        I::Item: Clone,
+        F: FnMut(I::Item) -> Option<B>,
     {
-        Lob::new(WindowIterator::new(self.iter, n))
+        Lob::new(self.iter.map_while(f))
     }
 
-    /// Group elements by a key function
+    /// Map each element to an iterable and flatten the results
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let result: Vec<_> = vec![1, 2, 3, 4, 5, 6]
+    /// let result: Vec<_> = vec!["a b", "c"]
     ///     .into_iter()
     ///     .lob()
-    ///     .group_by(|x| x % 2)
+    ///     .flat_map(|s| s.split(' '))
     ///     .collect();
     ///
-    /// // Result contains (key, group) pairs
-    /// assert_eq!(result.len(), 2);
+    /// assert_eq!(result, vec!["a", "b", "c"]);
     /// ```
     #[must_use]
-    pub fn group_by<K, F>(self, key_fn: F) -> Lob<impl Iterator<Item = (K, Vec<I::Item>)>>
+    pub fn flat_map<F, U>(self, f: F) -> Lob<impl Iterator<Item = U::Item>>
     where
-        K: Eq + Hash,
-        F: FnMut(&I::Item) -> K,
+        F: FnMut(I::Item) -> U,
+        U: IntoIterator,
     {
-        Lob::new(GroupByCollectIterator::new(self.iter, key_fn))
+        Lob::new(self.iter.flat_map(f))
     }
 
-    // ========== Join Operations ==========
-
-    /// Inner join with another iterator based on key functions
+    /// Apply `f` to every value of each row, keeping the keys
+    ///
+    /// For streams of `HashMap<String, String>` rows, such as `--parse-csv`
+    /// input.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
+    /// use std::collections::HashMap;
     ///
-    /// let left = vec![(1, "a"), (2, "b"), (3, "c")];
-    /// let right = vec![(1, "x"), (2, "y"), (4, "z")];
-    ///
-    /// let result: Vec<_> = left
+    /// let row = HashMap::from([("name".to_string(), " ada ".to_string())]);
+    /// let result: Vec<_> = vec![row]
     ///     .into_iter()
     ///     .lob()
-    ///     .join_inner(right, |x| x.0, |x| x.0)
+    ///     .map_values(|v| v.trim().to_string())
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![((1, "a"), (1, "x")), ((2, "b"), (2, "y"))]);
+    /// assert_eq!(result[0]["name"], "ada");
     /// ```
     #[must_use]
-    pub fn join_inner<J, K, FL, FR>(
-        self,
-        other: J,
-        left_key: FL,
-        right_key: FR,
-    ) -> Lob<impl Iterator<Item = (I::Item, J::Item)>>
+    pub fn map_values<F>(self, mut f: F) -> Lob<impl Iterator<Item = HashMap<String, String>>>
     where
-        I::Item: Clone,
-        J: IntoIterator,
-        J::Item: Clone,
-        K: Eq + Hash,
-        FL: Fn(&I::Item) -> K,
-        FR: Fn(&J::Item) -> K,
+        I: Iterator<Item = HashMap<String, String>>,
+        F: FnMut(String) -> String,
     {
-        Lob::new(InnerJoinIterator::new(
-            self.iter, other, left_key, right_key,
-        ))
+        Lob::new(
+            self.iter
+                .map(move |row| row.into_iter().map(|(k, v)| (k, f(v))).collect()),
+        )
     }
 
-    /// Left join with another iterator based on key functions
+    /// Apply `f` to every key of each row, keeping the values
+    ///
+    /// If two keys map to the same new key, one of their values is kept.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
+    /// use std::collections::HashMap;
     ///
-    /// let left = vec![(1, "a"), (2, "b"), (3, "c")];
-    /// let right = vec![(1, "x"), (2, "y")];
-    ///
-    /// let result: Vec<_> = left
+    /// let row = HashMap::from([("Name".to_string(), "ada".to_string())]);
+    /// let result: Vec<_> = vec![row]
     ///     .into_iter()
     ///     .lob()
-    ///     .join_left(right, |x| x.0, |x| x.0)
+    ///     .map_keys(|k| k.to_lowercase())
     ///     .collect();
     ///
-    /// assert_eq!(result.len(), 3);  // All left items preserved
+    /// assert_eq!(result[0]["name"], "ada");
     /// ```
     #[must_use]
-    pub fn join_left<J, K, FL, FR>(
-        self,
-        other: J,
-        left_key: FL,
-        right_key: FR,
-    ) -> Lob<impl Iterator<Item = (I::Item, Option<J::Item>)>>
+    pub fn map_keys<F>(self, mut f: F) -> Lob<impl Iterator<Item = HashMap<String, String>>>
     where
-        I::Item: Clone,
-        J: IntoIterator,
-        J::Item: Clone,
-        K: Eq + Hash,
-        FL: Fn(&I::Item) -> K,
-        FR: Fn(&J::Item) -> K,
+        I: Iterator<Item = HashMap<String, String>>,
+        F: FnMut(String) -> String,
     {
-        Lob::new(LeftJoinIterator::new(self.iter, other, left_key, right_key))
+        Lob::new(
+            self.iter
+                .map(move |row| row.into_iter().map(|(k, v)| (f(k), v)).collect()),
+        )
     }
 
-    // ========== Terminal Operations (consume iterator) ==========
-
-    /// Collect into a collection
+    /// Rename the column `old` to `new` in each row
+    ///
+    /// Rows without `old` pass through unchanged; an existing `new` value is
+    /// replaced.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
+    /// use std::collections::HashMap;
     ///
-    /// let result: Vec<_> = (0..5)
+    /// let row = HashMap::from([("mail".to_string(), "ada@example.com".to_string())]);
+    /// let result: Vec<_> = vec![row]
+    ///     .into_iter()
     ///     .lob()
-    ///     .filter(|x| x % 2 == 0)
+    ///     .rename_key("mail", "email")
     ///     .collect();
     ///
-    /// assert_eq!(result, vec![0, 2, 4]);
+    /// assert_eq!(result[0]["email"], "ada@example.com");
+    /// assert!(!result[0].contains_key("mail"));
     /// ```
-    pub fn collect<B: FromIterator<I::Item>>(self) -> B {
-        self.iter.collect()
+    #[must_use]
+    pub fn rename_key(
+        self,
+        old: &str,
+        new: &str,
+    ) -> Lob<impl Iterator<Item = HashMap<String, String>>>
+    where
+        I: Iterator<Item = HashMap<String, String>>,
+    {
+        let old = old.to_string();
+        let new = new.to_string();
+        Lob::new(self.iter.map(move |mut row| {
+            if let Some(value) = row.remove(&old) {
+                row.insert(new.clone(), value);
+            }
+            row
+        }))
     }
 
-    /// Count the number of elements
+    /// Add index to each element
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let count = (0..10)
+    /// let result: Vec<_> = vec!["a", "b", "c"]
+    ///     .into_iter()
     ///     .lob()
-    ///     .filter(|x| x % 2 == 0)
-    ///     .count();
+    ///     .enumerate()
+    ///     .collect();
     ///
-    /// assert_eq!(count, 5);
+    /// assert_eq!(result, vec![(0, "a"), (1, "b"), (2, "c")]);
     /// ```
-    pub fn count(self) -> usize {
-        self.iter.count()
+    #[must_use]
+    pub fn enumerate(self) -> Lob<impl Iterator<Item = (usize, I::Item)>> {
+        Lob::new(self.iter.enumerate())
     }
 
-    /// Sum all elements
+    /// Add index to each element, counting from `start`
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let sum = (1..=5).lob().sum::<i32>();
+    /// let result: Vec<_> = vec!["a", "b"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .enumerate_from(1)
+    ///     .collect();
     ///
-    /// assert_eq!(sum, 15);
+    /// assert_eq!(result, vec![(1, "a"), (2, "b")]);
     /// ```
-    pub fn sum<S>(self) -> S
-    where
-        S: std::iter::Sum<I::Item>,
-    {
-        self.iter.sum()
+    #[must_use]
+    pub fn enumerate_from(self, start: usize) -> Lob<impl Iterator<Item = (usize, I::Item)>> {
+        Lob::new(self.iter.enumerate().map(move |(i, x)| (i + start, x)))
     }
 
-    /// Find the minimum element
+    /// Zip with another iterator
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let min = vec![3, 1, 4, 1, 5].into_iter().lob().min();
+    /// let result: Vec<_> = vec![1, 2, 3]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .zip(vec!["a", "b", "c"])
+    ///     .collect();
     ///
-    /// assert_eq!(min, Some(1));
+    /// assert_eq!(result, vec![(1, "a"), (2, "b"), (3, "c")]);
     /// ```
-    pub fn min(self) -> Option<I::Item>
+    #[must_use]
+    pub fn zip<J>(self, other: J) -> Lob<impl Iterator<Item = (I::Item, J::Item)>>
     where
-        I::Item: Ord,
+        J: IntoIterator,
     {
-        self.iter.min()
+        Lob::new(self.iter.zip(other))
     }
 
-    /// Find the maximum element
+    /// Zip with another iterator, combining each pair with a function
+    ///
+    /// Stops at the end of the shorter input, like [`zip`](Self::zip).
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let max = vec![3, 1, 4, 1, 5].into_iter().lob().max();
+    /// let result: Vec<_> = vec![1, 2, 3]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .zip_with(vec![10, 20, 30], |a, b| a + b)
+    ///     .collect();
     ///
-    /// assert_eq!(max, Some(5));
+    /// assert_eq!(result, vec![11, 22, 33]);
     /// ```
-    pub fn max(self) -> Option<I::Item>
+    #[must_use]
+    pub fn zip_with<J, F, B>(self, other: J, mut f: F) -> Lob<impl Iterator<Item = B>>
     where
-        I::Item: Ord,
+        J: IntoIterator,
+        F: FnMut(I::Item, J::Item) -> B,
     {
-        self.iter.max()
+        Lob::new(self.iter.zip(other).map(move |(a, b)| f(a, b)))
     }
 
-    /// Get the first element
+    /// Merge with another sorted iterator, producing one sorted stream
+    ///
+    /// Both inputs must already be sorted in ascending order; unsorted input
+    /// produces an unspecified (but complete) interleaving. On ties, elements
+    /// from `self` come first.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let first = (1..10).lob().first();
+    /// let result: Vec<_> = vec![1, 4, 6]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .merge(vec![2, 3, 7])
+    ///     .collect();
     ///
-    /// assert_eq!(first, Some(1));
+    /// assert_eq!(result, vec![1, 2, 3, 4, 6, 7]);
     /// ```
-    pub fn first(mut self) -> Option<I::Item> {
-        self.iter.next()
+    #[must_use]
+    pub fn merge<J>(self, other: J) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Ord,
+        J: IntoIterator<Item = I::Item>,
+    {
+        Lob::new(MergeIterator::new(self.iter, other.into_iter()))
     }
 
-    /// Get the last element
+    /// Alternate elements from this iterator and another
+    ///
+    /// Starts with `self`; once either side runs out, the rest of the other
+    /// side follows in order.
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let last = (1..10).lob().last();
+    /// let result: Vec<_> = vec![1, 3, 5, 7]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .interleave(vec![2, 4])
+    ///     .collect();
     ///
-    /// assert_eq!(last, Some(9));
+    /// assert_eq!(result, vec![1, 2, 3, 4, 5, 7]);
     /// ```
-    pub fn last(self) -> Option<I::Item> {
-        self.iter.last()
+    #[must_use]
+    pub fn interleave<J>(self, other: J) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        J: IntoIterator<Item = I::Item>,
+    {
+        Lob::new(InterleaveIterator::new(self.iter, other.into_iter()))
     }
 
-    /// Reduce to a single value
+    /// Flatten nested iterators
     ///
     /// # Examples
     ///
     /// ```
     /// use lob_core::LobExt;
     ///
-    /// let product = (1..=5).lob().reduce(|a, b| a * b);
+    /// let result: Vec<_> = vec![vec![1, 2], vec![3, 4]]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .flatten()
+    ///     .collect();
     ///
-    /// assert_eq!(product, Some(120));
+    /// assert_eq!(result, vec![1, 2, 3, 4]);
+    /// ```
+    #[must_use]
+    pub fn flatten<T>(self) -> Lob<impl Iterator<Item = T>>
+    where
+        I::Item: IntoIterator<Item = T>,
+    {
+        Lob::new(self.iter.flatten())
+    }
+
+    /// Insert a separator between every pair of elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: String = vec!["a", "b", "c"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .intersperse(", ")
+    ///     .collect();
+    ///
+    /// assert_eq!(result, "a, b, c");
+    /// ```
+    #[must_use]
+    pub fn intersperse(self, separator: I::Item) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Clone,
+    {
+        Lob::new(IntersperseIterator::new(self.iter, separator))
+    }
+
+    /// Repeat the sequence exactly `times` times
+    ///
+    /// This is eager: the source is collected up front when `cycle_n` is
+    /// called, even for `times == 0`, so it is read only once. Unlike an
+    /// unbounded `cycle`, this always terminates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result = vec![1, 2].into_iter().lob().cycle_n(3).to_list();
+    ///
+    /// assert_eq!(result, vec![1, 2, 1, 2, 1, 2]);
+    /// ```
+    #[must_use]
+    pub fn cycle_n(self, times: usize) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Clone,
+    {
+        let items: Vec<I::Item> = self.iter.collect();
+        Lob::new((0..times).flat_map(move |_| items.clone()))
+    }
+
+    /// Yield every `k`-element combination of the elements
+    ///
+    /// This is eager: all elements are collected first. Combinations come in
+    /// lexicographic order of element positions, each keeping input order.
+    /// There are `n! / (k! (n - k)!)` of them, which grows very quickly, so
+    /// keep the input small. `k = 0` yields one empty combination and
+    /// `k > n` yields none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec!['a', 'b', 'c'].into_iter().lob().combinations(2).collect();
+    ///
+    /// assert_eq!(result, vec![vec!['a', 'b'], vec!['a', 'c'], vec!['b', 'c']]);
+    /// ```
+    #[must_use]
+    pub fn combinations(self, k: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        I::Item: Clone,
+    {
+        let items: Vec<I::Item> = self.iter.collect();
+        Lob::new(items.into_iter().combinations(k))
+    }
+
+    /// Yield every ordered arrangement of `k` distinct elements
+    ///
+    /// This is eager: all elements are collected first. Permutations come in
+    /// lexicographic order of element positions. There are `n! / (n - k)!` of
+    /// them, which is factorial in the input size, so keep the input small.
+    /// `k = 0` yields one empty permutation and `k > n` yields none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 2].into_iter().lob().permutations(2).collect();
+    ///
+    /// assert_eq!(result, vec![vec![1, 2], vec![2, 1]]);
+    /// ```
+    #[must_use]
+    pub fn permutations(self, k: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        I::Item: Clone,
+    {
+        let items: Vec<I::Item> = self.iter.collect();
+        Lob::new(items.into_iter().permutations(k))
+    }
+
+    /// Treat the elements as rows of a matrix and yield its columns
+    ///
+    /// This is eager: all rows are collected first. Ragged input is truncated
+    /// to the length of the shortest row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![vec![1, 2, 3], vec![4, 5, 6]]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .transpose()
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    /// ```
+    #[must_use]
+    pub fn transpose<T>(self) -> Lob<impl Iterator<Item = Vec<T>>>
+    where
+        I::Item: IntoIterator<Item = T>,
+    {
+        let rows: Vec<Vec<T>> = self.iter.map(|row| row.into_iter().collect()).collect();
+        let width = rows.iter().map(Vec::len).min().unwrap_or(0);
+
+        let mut columns: Vec<Vec<T>> = (0..width).map(|_| Vec::with_capacity(rows.len())).collect();
+        for row in rows {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+        Lob::new(columns.into_iter())
+    }
+
+    /// Sort the elements with a comparator closure
+    ///
+    /// This is eager: all elements are collected, then sorted with a stable
+    /// sort, so elements that compare equal keep their original order. Use it
+    /// for values that are not `Ord`, such as floats, or to reverse the order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![2.5, -1.0, 0.5]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .sorted_by(|a: &f64, b| a.total_cmp(b))
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![-1.0, 0.5, 2.5]);
+    /// ```
+    #[must_use]
+    pub fn sorted_by<F>(self, mut cmp: F) -> Lob<std::vec::IntoIter<I::Item>>
+    where
+        F: FnMut(&I::Item, &I::Item) -> std::cmp::Ordering,
+    {
+        let mut items: Vec<I::Item> = self.iter.collect();
+        items.sort_by(|a, b| cmp(a, b));
+        Lob::new(items.into_iter())
+    }
+
+    /// Yield the elements in a random order determined by `seed`
+    ///
+    /// This is eager: all elements are collected, then shuffled with
+    /// Fisher–Yates. The same seed over the same input always gives the same
+    /// order; follow with `take(n)` for an ordered random sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let mut result: Vec<_> = (1..=5).lob().shuffle(42).collect();
+    ///
+    /// assert_eq!(result, (1..=5).lob().shuffle(42).collect::<Vec<_>>());
+    /// result.sort_unstable();
+    /// assert_eq!(result, vec![1, 2, 3, 4, 5]);
+    /// ```
+    #[must_use]
+    pub fn shuffle(self, seed: u64) -> Lob<std::vec::IntoIter<I::Item>> {
+        let mut items: Vec<I::Item> = self.iter.collect();
+        let mut rng = SplitMix64::new(seed);
+        for i in (1..items.len()).rev() {
+            items.swap(i, rng.below(i + 1));
+        }
+        Lob::new(items.into_iter())
+    }
+
+    /// Pair each element with its 1-based rank by value, in the original order
+    ///
+    /// This is eager: all elements are collected and a sorted copy is made.
+    /// Equal elements share the lowest rank, and the next distinct value skips
+    /// ahead ("1224" competition ranking).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![30, 10, 20, 10].into_iter().lob().rank().collect();
+    ///
+    /// assert_eq!(result, vec![(4, 30), (1, 10), (3, 20), (1, 10)]);
+    /// ```
+    #[must_use]
+    pub fn rank(self) -> Lob<impl Iterator<Item = (usize, I::Item)>>
+    where
+        I::Item: Ord + Clone,
+    {
+        let items: Vec<I::Item> = self.iter.collect();
+        let mut sorted = items.clone();
+        sorted.sort_unstable();
+        Lob::new(items.into_iter().map(move |item| {
+            let below = sorted.partition_point(|x| *x < item);
+            (below + 1, item)
+        }))
+    }
+
+    /// Pass a clone of each element to `sink` while forwarding the original
+    ///
+    /// Unlike `Iterator::inspect`, the sink receives owned values, so it can
+    /// store them or feed a side aggregate without cloning itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let mut seen = Vec::new();
+    /// let doubled: Vec<_> = (1..=3).lob().tee(|x| seen.push(x)).map(|x| x * 2).collect();
+    ///
+    /// assert_eq!(doubled, vec![2, 4, 6]);
+    /// assert_eq!(seen, vec![1, 2, 3]);
+    /// ```
+    #[must_use]
+    pub fn tee<F>(self, mut sink: F) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Clone,
+        F: FnMut(I::Item),
+    {
+        Lob::new(self.iter.inspect(move |item| sink(item.clone())))
+    }
+
+    /// Forward elements unchanged, printing `processed: <n>` to stderr every `every` items
+    ///
+    /// A lightweight progress indicator for long pipelines; stdout is left
+    /// untouched. Use `inspect_count_to` to send the lines elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// // Prints "processed: 500" and "processed: 1000" to stderr
+    /// let total: i32 = (0..1000).lob().inspect_count(500).sum();
+    ///
+    /// assert_eq!(total, 499_500);
+    /// ```
+    #[must_use]
+    pub fn inspect_count(self, every: usize) -> Lob<impl Iterator<Item = I::Item>> {
+        self.inspect_count_to(every, std::io::stderr())
+    }
+
+    /// Like `inspect_count`, but write the progress lines to `sink`
+    ///
+    /// Write errors are ignored so that reporting never interrupts the pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let mut log = Vec::new();
+    /// let count = (0..5).lob().inspect_count_to(2, &mut log).count();
+    ///
+    /// assert_eq!(count, 5);
+    /// assert_eq!(String::from_utf8(log).unwrap(), "processed: 2\nprocessed: 4\n");
+    /// ```
+    #[must_use]
+    pub fn inspect_count_to<W: std::io::Write>(
+        self,
+        every: usize,
+        mut sink: W,
+    ) -> Lob<impl Iterator<Item = I::Item>> {
+        assert!(every > 0, "inspect_count every must be greater than 0");
+        let mut seen = 0usize;
+        Lob::new(self.iter.inspect(move |_| {
+            seen += 1;
+            if seen.is_multiple_of(every) {
+                let _ = writeln!(sink, "processed: {}", seen);
+            }
+        }))
+    }
+
+    /// Like `fold`, but yield every intermediate accumulator
+    ///
+    /// The first item is `init` itself, so the output is one longer than the
+    /// input. `cumulative_sum` is the special case of addition from zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (1..=4).lob().accumulate(1, |acc, x| acc * x).collect();
+    ///
+    /// assert_eq!(result, vec![1, 1, 2, 6, 24]);
+    /// ```
+    #[must_use]
+    pub fn accumulate<B, F>(self, init: B, mut f: F) -> Lob<impl Iterator<Item = B>>
+    where
+        B: Clone,
+        F: FnMut(B, I::Item) -> B,
+    {
+        let rest = self.iter.scan(Some(init.clone()), move |state, item| {
+            let next = f(state.take()?, item);
+            *state = Some(next.clone());
+            Some(next)
+        });
+        Lob::new(std::iter::once(init).chain(rest))
+    }
+
+    // ========== Numeric Operations (lazy) ==========
+
+    /// Moving average over a sliding window of size n
+    ///
+    /// A running sum is maintained, so each step is O(1). Like `window`, nothing
+    /// is yielded until the window is full, so inputs shorter than n yield nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 2, 3, 4]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .rolling_mean(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![1.5, 2.5, 3.5]);
+    /// ```
+    #[must_use]
+    pub fn rolling_mean(self, window: usize) -> Lob<impl Iterator<Item = f64>>
+    where
+        I::Item: Into<f64>,
+    {
+        Lob::new(RollingMeanIterator::new(self.iter, window))
+    }
+
+    /// Exponentially weighted moving average with smoothing factor `alpha`
+    ///
+    /// The first element seeds the average; each later output is
+    /// `alpha * x + (1 - alpha) * previous`. Larger `alpha` follows the input
+    /// more closely, and `alpha = 1.0` reproduces it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![10, 20, 20]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .ewma(0.5)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![10.0, 15.0, 17.5]);
+    /// ```
+    #[must_use]
+    pub fn ewma(self, alpha: f64) -> Lob<impl Iterator<Item = f64>>
+    where
+        I::Item: Into<f64>,
+    {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "ewma alpha must be in (0, 1], got {}",
+            alpha
+        );
+        Lob::new(self.iter.scan(None, move |average: &mut Option<f64>, x| {
+            let x: f64 = x.into();
+            let next = average.map_or(x, |prev| alpha.mul_add(x - prev, prev));
+            *average = Some(next);
+            Some(next)
+        }))
+    }
+
+    /// Running total of the elements seen so far
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 2, 3]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .cumulative_sum()
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![1, 3, 6]);
+    /// ```
+    #[must_use]
+    pub fn cumulative_sum(self) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Copy + std::ops::Add<Output = I::Item> + Default,
+    {
+        Lob::new(self.iter.scan(I::Item::default(), |total, x| {
+            *total = *total + x;
+            Some(*total)
+        }))
+    }
+
+    /// Running maximum of the elements seen so far
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 3, 2, 5]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .cumulative_max()
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![1, 3, 3, 5]);
+    /// ```
+    #[must_use]
+    pub fn cumulative_max(self) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: PartialOrd + Clone,
+    {
+        self.running_extremum(|x, best| x > best)
+    }
+
+    /// Running minimum of the elements seen so far
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![4, 2, 3, 1]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .cumulative_min()
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![4, 2, 2, 1]);
+    /// ```
+    #[must_use]
+    pub fn cumulative_min(self) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: PartialOrd + Clone,
+    {
+        self.running_extremum(|x, best| x < best)
+    }
+
+    /// Yield the best element so far at each position, as judged by `better`
+    fn running_extremum<F>(self, mut better: F) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Clone,
+        F: FnMut(&I::Item, &I::Item) -> bool,
+    {
+        Lob::new(self.iter.scan(None, move |best: &mut Option<I::Item>, x| {
+            match best {
+                Some(current) if !better(&x, current) => {}
+                _ => *best = Some(x),
+            }
+            best.clone()
+        }))
+    }
+
+    /// Differences between consecutive elements (`next - prev`)
+    ///
+    /// Yields one fewer element than the input; empty and single-element
+    /// inputs yield nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 4, 9]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .diff()
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![3, 5]);
+    /// ```
+    #[must_use]
+    pub fn diff(self) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: Copy + std::ops::Sub<Output = I::Item>,
+    {
+        Lob::new(DiffIterator::new(self.iter))
+    }
+
+    /// Restrict each element to the range `[lo, hi]`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo` is greater than `hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![-5, 3, 42].into_iter().lob().clamp(0, 10).collect();
+    ///
+    /// assert_eq!(result, vec![0, 3, 10]);
+    /// ```
+    #[must_use]
+    pub fn clamp(self, lo: I::Item, hi: I::Item) -> Lob<impl Iterator<Item = I::Item>>
+    where
+        I::Item: PartialOrd + Clone,
+    {
+        assert!(lo <= hi, "clamp requires lo <= hi");
+        Lob::new(self.iter.map(move |x| {
+            if x < lo {
+                lo.clone()
+            } else if x > hi {
+                hi.clone()
+            } else {
+                x
+            }
+        }))
+    }
+
+    // ========== Grouping Operations ==========
+
+    /// Group elements into chunks of size n
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (0..5)
+    ///     .lob()
+    ///     .chunk(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec![0, 1], vec![2, 3], vec![4]]);
+    /// ```
+    #[must_use]
+    pub fn chunk(self, n: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>> {
+        Lob::new(ChunkIterator::new(self.iter, n))
+    }
+
+    /// Group elements into chunks of size n, dropping a short final chunk
+    ///
+    /// Unlike [`chunk`](Self::chunk), every emitted chunk has exactly `n`
+    /// elements, which suits pairing up fixed-size records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 2, 3, 4, 5]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .chunk_exact(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec![1, 2], vec![3, 4]]);
+    /// ```
+    #[must_use]
+    pub fn chunk_exact(self, n: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>> {
+        Lob::new(ChunkIterator::new(self.iter, n).take_while(move |chunk| chunk.len() == n))
+    }
+
+    /// Group consecutive elements into chunks while a predicate holds
+    ///
+    /// `predicate(prev, cur)` is called for each adjacent pair; a new chunk is
+    /// started whenever it returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 2, 3, 2, 1, 4]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .chunk_while(|prev, cur| cur > prev)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec![1, 2, 3], vec![2], vec![1, 4]]);
+    /// ```
+    #[must_use]
+    pub fn chunk_while<F>(self, predicate: F) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        F: FnMut(&I::Item, &I::Item) -> bool,
+    {
+        Lob::new(ChunkWhileIterator::new(self.iter, predicate))
+    }
+
+    /// Group consecutive elements into chunks that share the same key
+    ///
+    /// A new chunk is started whenever `key_fn` returns a value different from
+    /// the previous element's. Only adjacent elements are grouped, so the input
+    /// should already be sorted by the key; unlike `group_by`, no hash map is
+    /// built and the output streams.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec!["a1", "a2", "b1", "a3"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .chunk_on_change(|s| s.chars().next())
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec!["a1", "a2"], vec!["b1"], vec!["a3"]]);
+    /// ```
+    #[must_use]
+    pub fn chunk_on_change<K, F>(self, key_fn: F) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        K: PartialEq,
+        F: FnMut(&I::Item) -> K,
+    {
+        Lob::new(ChunkOnChangeIterator::new(self.iter, key_fn))
+    }
+
+    /// Group consecutive elements into batches whose total size stays within `max_size`
+    ///
+    /// Elements are added to the current batch until the next one would push
+    /// the sum of `size_of` over `max_size`. An element that is larger than
+    /// `max_size` on its own is emitted as a single-element batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec!["aaaa", "bb", "cc", "dddddddd", "e"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .batch_by_size(6, |s| s.len())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     result,
+    ///     vec![vec!["aaaa", "bb"], vec!["cc"], vec!["dddddddd"], vec!["e"]]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn batch_by_size<F>(
+        self,
+        max_size: usize,
+        size_of: F,
+    ) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        F: FnMut(&I::Item) -> usize,
+    {
+        Lob::new(BatchBySizeIterator::new(self.iter, max_size, size_of))
+    }
+
+    /// Split the stream into groups at delimiter elements
+    ///
+    /// Mirrors `str::split`: delimiters are dropped, and leading, trailing or
+    /// consecutive delimiters produce empty groups. An empty stream yields no groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec!["a", "b", "", "c"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .split_on(|x| x.is_empty())
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec!["a", "b"], vec!["c"]]);
+    /// ```
+    #[must_use]
+    pub fn split_on<F>(self, is_delim: F) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        F: FnMut(&I::Item) -> bool,
+    {
+        Lob::new(SplitOnIterator::new(self.iter, is_delim))
+    }
+
+    /// Compress runs of equal consecutive elements into `(value, run_length)` pairs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec!["a", "a", "b", "a"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .run_length_encode()
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![("a", 2), ("b", 1), ("a", 1)]);
+    /// ```
+    #[must_use]
+    pub fn run_length_encode(self) -> Lob<impl Iterator<Item = (I::Item, usize)>>
+    where
+        I::Item: PartialEq,
+    {
+        Lob::new(RunLengthIterator::new(self.iter))
+    }
+
+    /// Create sliding windows of size n
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (1..=4)
+    ///     .lob()
+    ///     .window(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec![1, 2], vec![2, 3], vec![3, 4]]);
+    /// ```
+    #[must_use]
+    pub fn window(self, n: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        I::Item: Clone,
+    {
+        Lob::new(WindowIterator::new(self.iter, n))
+    }
+
+    /// Create windows of `size` elements that advance by `step` each time
+    ///
+    /// `step < size` gives overlapping windows (`window(n)` is `step == 1`),
+    /// `step == size` tiles the input like `chunk_exact`, and `step > size`
+    /// skips the elements between windows. A trailing partial window is
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `step` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (1..=7)
+    ///     .lob()
+    ///     .window_step(3, 2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![vec![1, 2, 3], vec![3, 4, 5], vec![5, 6, 7]]);
+    /// ```
+    #[must_use]
+    pub fn window_step(self, size: usize, step: usize) -> Lob<impl Iterator<Item = Vec<I::Item>>>
+    where
+        I::Item: Clone,
+    {
+        Lob::new(WindowStepIterator::new(self.iter, size, step))
+    }
+
+    /// Group elements by a key function
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec![1, 2, 3, 4, 5, 6]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .group_by(|x| x % 2)
+    ///     .collect();
+    ///
+    /// // Result contains (key, group) pairs
+    /// assert_eq!(result.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn group_by<K, F>(self, key_fn: F) -> Lob<impl Iterator<Item = (K, Vec<I::Item>)>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&I::Item) -> K,
+    {
+        Lob::new(GroupByCollectIterator::new(self.iter, key_fn))
+    }
+
+    /// Group elements by a key function, keeping at most `max_groups` groups
+    ///
+    /// Memory stays bounded by the number of groups: once `max_groups`
+    /// distinct keys have been seen, items with any new key are routed to a
+    /// single overflow bucket and a warning is printed to stderr. Groups are
+    /// keyed `Some(key)` and the overflow bucket is keyed `None`, so it can't
+    /// collide with a real key. The overflow bucket comes last; other groups
+    /// are in hash order, as with `group_by`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let mut result: Vec<_> = vec!["a", "b", "a", "c", "d"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .group_by_bounded(|s| s.to_string(), 2)
+    ///     .collect();
+    ///
+    /// assert_eq!(result.pop(), Some((None, vec!["c", "d"])));
+    /// result.sort();
+    /// assert_eq!(
+    ///     result,
+    ///     vec![(Some("a".to_string()), vec!["a", "a"]), (Some("b".to_string()), vec!["b"])]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn group_by_bounded<K, F>(
+        self,
+        key_fn: F,
+        max_groups: usize,
+    ) -> Lob<impl Iterator<Item = (Option<K>, Vec<I::Item>)>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&I::Item) -> K,
+    {
+        Lob::new(GroupByBoundedIterator::new(self.iter, key_fn, max_groups))
+    }
+
+    /// Group elements by a key function, yielding groups in first-seen key order
+    ///
+    /// Unlike `group_by`, whose group order depends on hashing, the output is
+    /// deterministic: the group whose key appears first in the input comes
+    /// first. Items within a group keep their input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = vec!["bob", "alice", "bart", "anna"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .group_by_ordered(|s| s.chars().next().unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![('b', vec!["bob", "bart"]), ('a', vec!["alice", "anna"])]);
+    /// ```
+    #[must_use]
+    pub fn group_by_ordered<K, F>(self, key_fn: F) -> Lob<impl Iterator<Item = (K, Vec<I::Item>)>>
+    where
+        K: Eq + Hash + Clone,
+        F: FnMut(&I::Item) -> K,
+    {
+        Lob::new(GroupByOrderedIterator::new(self.iter, key_fn))
+    }
+
+    /// Group elements by a key function and fold each group into an accumulator
+    ///
+    /// Aggregates in a single pass without materializing a `Vec` per group,
+    /// unlike `group_by`. `init` creates the starting accumulator for each new key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let people = vec![("nyc", 31), ("sf", 25), ("nyc", 42), ("sf", 38)];
+    /// let oldest = people
+    ///     .into_iter()
+    ///     .lob()
+    ///     .group_by_agg(|(city, _)| *city, || 0, |max, (_, age)| max.max(age));
+    ///
+    /// assert_eq!(oldest["nyc"], 42);
+    /// assert_eq!(oldest["sf"], 38);
+    /// ```
+    pub fn group_by_agg<K, F, A, Init, Fold>(
+        self,
+        mut key_fn: F,
+        init: Init,
+        mut fold: Fold,
+    ) -> HashMap<K, A>
+    where
+        K: Eq + Hash,
+        F: FnMut(&I::Item) -> K,
+        Init: Fn() -> A,
+        Fold: FnMut(A, I::Item) -> A,
+    {
+        let mut groups = HashMap::new();
+        for item in self.iter {
+            let key = key_fn(&item);
+            let acc = groups.remove(&key).unwrap_or_else(&init);
+            groups.insert(key, fold(acc, item));
+        }
+        groups
+    }
+
+    /// Split elements into buckets keyed by a function
+    ///
+    /// Like `group_by`, but collected into a map so buckets can be indexed
+    /// directly. Items keep their input order within each bucket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let buckets = (1..=6).lob().partition_by(|x| x % 3);
+    ///
+    /// assert_eq!(buckets[&0], vec![3, 6]);
+    /// assert_eq!(buckets[&1], vec![1, 4]);
+    /// ```
+    pub fn partition_by<K, F>(self, mut f: F) -> HashMap<K, Vec<I::Item>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&I::Item) -> K,
+    {
+        let mut buckets: HashMap<K, Vec<I::Item>> = HashMap::new();
+        for item in self.iter {
+            buckets.entry(f(&item)).or_default().push(item);
+        }
+        buckets
+    }
+
+    /// Find the maximum value per key in a single pass
+    ///
+    /// `val_fn` extracts the value compared within each group. Values that are
+    /// not comparable (such as `NaN`) never replace the current maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let requests = vec![("/a", 120), ("/b", 40), ("/a", 310), ("/b", 95)];
+    /// let slowest = requests
+    ///     .into_iter()
+    ///     .lob()
+    ///     .max_by_group(|(path, _)| *path, |(_, ms)| *ms);
+    ///
+    /// assert_eq!(slowest["/a"], 310);
+    /// assert_eq!(slowest["/b"], 95);
+    /// ```
+    pub fn max_by_group<K, V, FK, FV>(self, mut key_fn: FK, mut val_fn: FV) -> HashMap<K, V>
+    where
+        K: Eq + Hash,
+        V: PartialOrd,
+        FK: FnMut(&I::Item) -> K,
+        FV: FnMut(&I::Item) -> V,
+    {
+        let mut maxima: HashMap<K, V> = HashMap::new();
+        for item in self.iter {
+            let value = val_fn(&item);
+            match maxima.entry(key_fn(&item)) {
+                Entry::Occupied(mut current) => {
+                    if value > *current.get() {
+                        current.insert(value);
+                    }
+                }
+                Entry::Vacant(slot) => {
+                    slot.insert(value);
+                }
+            }
+        }
+        maxima
+    }
+
+    // ========== Join Operations ==========
+
+    /// Inner join with another iterator based on key functions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let left = vec![(1, "a"), (2, "b"), (3, "c")];
+    /// let right = vec![(1, "x"), (2, "y"), (4, "z")];
+    ///
+    /// let result: Vec<_> = left
+    ///     .into_iter()
+    ///     .lob()
+    ///     .join_inner(right, |x| x.0, |x| x.0)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![((1, "a"), (1, "x")), ((2, "b"), (2, "y"))]);
+    /// ```
+    #[must_use]
+    pub fn join_inner<J, K, FL, FR>(
+        self,
+        other: J,
+        left_key: FL,
+        right_key: FR,
+    ) -> Lob<impl Iterator<Item = (I::Item, J::Item)>>
+    where
+        I::Item: Clone,
+        J: IntoIterator,
+        J::Item: Clone,
+        K: Eq + Hash,
+        FL: Fn(&I::Item) -> K,
+        FR: Fn(&J::Item) -> K,
+    {
+        Lob::new(InnerJoinIterator::new(
+            self.iter, other, left_key, right_key,
+        ))
+    }
+
+    /// Left join with another iterator based on key functions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let left = vec![(1, "a"), (2, "b"), (3, "c")];
+    /// let right = vec![(1, "x"), (2, "y")];
+    ///
+    /// let result: Vec<_> = left
+    ///     .into_iter()
+    ///     .lob()
+    ///     .join_left(right, |x| x.0, |x| x.0)
+    ///     .collect();
+    ///
+    /// assert_eq!(result.len(), 3);  // All left items preserved
+    /// ```
+    #[must_use]
+    pub fn join_left<J, K, FL, FR>(
+        self,
+        other: J,
+        left_key: FL,
+        right_key: FR,
+    ) -> Lob<impl Iterator<Item = (I::Item, Option<J::Item>)>>
+    where
+        I::Item: Clone,
+        J: IntoIterator,
+        J::Item: Clone,
+        K: Eq + Hash,
+        FL: Fn(&I::Item) -> K,
+        FR: Fn(&J::Item) -> K,
+    {
+        Lob::new(LeftJoinIterator::new(self.iter, other, left_key, right_key))
+    }
+
+    // ========== Terminal Operations (consume iterator) ==========
+
+    /// Collect into a collection
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let result: Vec<_> = (0..5)
+    ///     .lob()
+    ///     .filter(|x| x % 2 == 0)
+    ///     .collect();
+    ///
+    /// assert_eq!(result, vec![0, 2, 4]);
+    /// ```
+    pub fn collect<B: FromIterator<I::Item>>(self) -> B {
+        self.iter.collect()
+    }
+
+    /// Count the number of elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let count = (0..10)
+    ///     .lob()
+    ///     .filter(|x| x % 2 == 0)
+    ///     .count();
+    ///
+    /// assert_eq!(count, 5);
+    /// ```
+    pub fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    /// Count the number of distinct elements
+    ///
+    /// Equivalent to `unique().count()`, but elements are moved straight into
+    /// a single `HashSet` rather than cloned for forwarding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let count = vec!["a", "b", "a", "c", "b"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .count_distinct();
+    ///
+    /// assert_eq!(count, 3);
+    /// ```
+    pub fn count_distinct(self) -> usize
+    where
+        I::Item: Eq + Hash,
+    {
+        self.iter.collect::<HashSet<_>>().len()
+    }
+
+    /// Pick `n` elements uniformly at random in a single pass
+    ///
+    /// Uses reservoir sampling, so only `n` elements are held in memory. The
+    /// same seed over the same input always picks the same elements. Streams
+    /// shorter than `n` are returned whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let picked = (0..1000).lob().sample_seeded(3, 42);
+    ///
+    /// assert_eq!(picked.len(), 3);
+    /// assert_eq!(picked, (0..1000).lob().sample_seeded(3, 42));
+    /// ```
+    pub fn sample_seeded(self, n: usize, seed: u64) -> Vec<I::Item> {
+        let mut rng = SplitMix64::new(seed);
+        let mut reservoir = Vec::with_capacity(n);
+        for (seen, item) in self.iter.enumerate() {
+            if reservoir.len() < n {
+                reservoir.push(item);
+            } else {
+                let slot = rng.below(seen + 1);
+                if slot < n {
+                    reservoir[slot] = item;
+                }
+            }
+        }
+        reservoir
+    }
+
+    /// Pick `n` elements at random, each with probability proportional to its weight
+    ///
+    /// Implements the A-Res weighted reservoir algorithm in one pass, holding
+    /// only `n` elements. Elements whose weight is not positive are never
+    /// picked. The result is in no particular order; the same seed over the
+    /// same input always picks the same elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let sizes = vec![("small", 1.0), ("huge", 1000.0), ("empty", 0.0)];
+    /// let picked = sizes.into_iter().lob().sample_weighted(1, 7, |(_, w)| *w);
+    ///
+    /// assert_eq!(picked, vec![("huge", 1000.0)]);
+    /// ```
+    pub fn sample_weighted<F>(self, n: usize, seed: u64, mut weight: F) -> Vec<I::Item>
+    where
+        F: FnMut(&I::Item) -> f64,
+    {
+        let mut rng = SplitMix64::new(seed);
+        // Each kept element carries its key ln(u) / w; the n largest keys win.
+        // The min-heap keeps the smallest kept key on top for O(log n) updates.
+        let mut reservoir = BinaryHeap::with_capacity(n);
+        for item in self.iter {
+            let w = weight(&item);
+            if w.is_nan() || w <= 0.0 {
+                continue;
+            }
+            let key = rng.next_f64().ln() / w;
+            if reservoir.len() < n {
+                reservoir.push(Reverse(WeightedItem { key, item }));
+            } else if let Some(mut smallest) = reservoir.peek_mut() {
+                if key > smallest.0.key {
+                    *smallest = Reverse(WeightedItem { key, item });
+                }
+            }
+        }
+        reservoir
+            .into_iter()
+            .map(|Reverse(slot)| slot.item)
+            .collect()
+    }
+
+    /// Sum all elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let sum = (1..=5).lob().sum::<i32>();
+    ///
+    /// assert_eq!(sum, 15);
+    /// ```
+    pub fn sum<S>(self) -> S
+    where
+        S: std::iter::Sum<I::Item>,
+    {
+        self.iter.sum()
+    }
+
+    /// Find the minimum element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let min = vec![3, 1, 4, 1, 5].into_iter().lob().min();
+    ///
+    /// assert_eq!(min, Some(1));
+    /// ```
+    pub fn min(self) -> Option<I::Item>
+    where
+        I::Item: Ord,
+    {
+        self.iter.min()
+    }
+
+    /// Find the maximum element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let max = vec![3, 1, 4, 1, 5].into_iter().lob().max();
+    ///
+    /// assert_eq!(max, Some(5));
+    /// ```
+    pub fn max(self) -> Option<I::Item>
+    where
+        I::Item: Ord,
+    {
+        self.iter.max()
+    }
+
+    /// Find both the minimum and maximum elements in a single pass
+    ///
+    /// Ties resolve like `min` and `max`: the first minimum and the last maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let extremes = vec![3, 1, 4, 1, 5].into_iter().lob().min_max();
+    ///
+    /// assert_eq!(extremes, Some((1, 5)));
+    /// ```
+    pub fn min_max(self) -> Option<(I::Item, I::Item)>
+    where
+        I::Item: Ord + Clone,
+    {
+        let mut iter = self.iter;
+        let first = iter.next()?;
+        Some(iter.fold((first.clone(), first), |(min, max), x| {
+            if x < min {
+                (x, max)
+            } else if x >= max {
+                (min, x)
+            } else {
+                (min, max)
+            }
+        }))
+    }
+
+    /// Find the n-th largest element (1-based)
+    ///
+    /// Uses a heap bounded to `n` elements instead of sorting the input. Equal
+    /// elements each count, so in `[5, 5, 3]` both the 1st and 2nd largest are
+    /// 5. Returns `None` if `n` is 0 or the input has fewer than `n` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let third = vec![3, 1, 4, 1, 5, 9].into_iter().lob().nth_largest(3);
+    ///
+    /// assert_eq!(third, Some(4));
+    /// ```
+    pub fn nth_largest(self, n: usize) -> Option<I::Item>
+    where
+        I::Item: Ord,
+    {
+        if n == 0 {
+            return None;
+        }
+        // Min-heap of the n largest seen so far; its top is the n-th largest
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+        for item in self.iter {
+            heap.push(Reverse(item));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        if heap.len() < n {
+            return None;
+        }
+        heap.pop().map(|Reverse(item)| item)
+    }
+
+    /// Find the n-th smallest element (1-based)
+    ///
+    /// The counterpart of `nth_largest`: a heap bounded to `n` elements, equal
+    /// elements each count, and `None` if `n` is 0 or out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let second = vec![3, 1, 4, 1, 5, 9].into_iter().lob().nth_smallest(2);
+    ///
+    /// assert_eq!(second, Some(1));
+    /// ```
+    pub fn nth_smallest(self, n: usize) -> Option<I::Item>
+    where
+        I::Item: Ord,
+    {
+        if n == 0 {
+            return None;
+        }
+        // Max-heap of the n smallest seen so far; its top is the n-th smallest
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+        for item in self.iter {
+            heap.push(item);
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        if heap.len() < n {
+            return None;
+        }
+        heap.pop()
+    }
+
+    /// Compute the p-th percentile (0.0–100.0) with linear interpolation
+    ///
+    /// This is eager: all elements are collected and sorted. Returns `None` for
+    /// an empty stream. `percentile(50.0)` is the median.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is outside `0.0..=100.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let p50 = vec![4, 1, 3, 2].into_iter().lob().percentile(50.0);
+    ///
+    /// assert_eq!(p50, Some(2.5));
+    /// ```
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn percentile(self, p: f64) -> Option<f64>
+    where
+        I::Item: Into<f64>,
+    {
+        assert!(
+            (0.0..=100.0).contains(&p),
+            "percentile must be between 0 and 100"
+        );
+
+        let mut values: Vec<f64> = self.iter.map(Into::into).collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let rank = p / 100.0 * (values.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let fraction = rank - lower as f64;
+        Some((values[upper] - values[lower]).mul_add(fraction, values[lower]))
+    }
+
+    /// Count elements into `bins` equal-width buckets over `[min, max]`
+    ///
+    /// Values below `min` land in the first bucket and values above `max` in
+    /// the last, so every element is counted exactly once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is 0 or if `max` is not greater than `min`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let counts = vec![1.0, 2.5, 2.7, 9.0].into_iter().lob().histogram(2, 0.0, 10.0);
+    ///
+    /// assert_eq!(counts, vec![3, 1]);
+    /// ```
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn histogram(self, bins: usize, min: f64, max: f64) -> Vec<usize>
+    where
+        I::Item: Into<f64>,
+    {
+        assert!(bins > 0, "histogram needs at least one bin");
+        assert!(max > min, "histogram max must be greater than min");
+
+        let width = (max - min) / bins as f64;
+        let last = (bins - 1) as f64;
+        let mut counts = vec![0; bins];
+        for value in self.iter {
+            let bucket = ((value.into() - min) / width).floor().clamp(0.0, last);
+            counts[bucket as usize] += 1;
+        }
+        counts
+    }
+
+    /// Find the most frequent element
+    ///
+    /// Ties resolve to the value seen first. Returns `None` for an empty stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let mode = vec!["a", "b", "b", "c"].into_iter().lob().mode();
+    ///
+    /// assert_eq!(mode, Some("b"));
+    /// ```
+    pub fn mode(self) -> Option<I::Item>
+    where
+        I::Item: Eq + Hash,
+    {
+        // item -> (count, index of first occurrence)
+        let mut counts: HashMap<I::Item, (usize, usize)> = HashMap::new();
+        for (idx, item) in self.iter.enumerate() {
+            counts.entry(item).or_insert((0, idx)).0 += 1;
+        }
+        counts
+            .into_iter()
+            .max_by(|(_, (count_a, first_a)), (_, (count_b, first_b))| {
+                count_a.cmp(count_b).then(first_b.cmp(first_a))
+            })
+            .map(|(item, _)| item)
+    }
+
+    /// Find the index of the maximum element
+    ///
+    /// Ties resolve to the first occurrence. Returns `None` for an empty stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let idx = vec![3, 7, 2, 7].into_iter().lob().argmax();
+    ///
+    /// assert_eq!(idx, Some(1));
+    /// ```
+    pub fn argmax(self) -> Option<usize>
+    where
+        I::Item: PartialOrd,
+    {
+        self.arg_extremum(|candidate, best| candidate > best)
+    }
+
+    /// Find the index of the minimum element
+    ///
+    /// Ties resolve to the first occurrence. Returns `None` for an empty stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let idx = vec![3, 1, 2, 1].into_iter().lob().argmin();
+    ///
+    /// assert_eq!(idx, Some(1));
+    /// ```
+    pub fn argmin(self) -> Option<usize>
+    where
+        I::Item: PartialOrd,
+    {
+        self.arg_extremum(|candidate, best| candidate < best)
+    }
+
+    /// Index of the first element that `better` prefers over all earlier ones
+    fn arg_extremum<F>(self, mut better: F) -> Option<usize>
+    where
+        F: FnMut(&I::Item, &I::Item) -> bool,
+    {
+        let mut best: Option<(usize, I::Item)> = None;
+        for (idx, item) in self.iter.enumerate() {
+            match &best {
+                Some((_, current)) if !better(&item, current) => {}
+                _ => best = Some((idx, item)),
+            }
+        }
+        best.map(|(idx, _)| idx)
+    }
+
+    /// Get the first element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let first = (1..10).lob().first();
+    ///
+    /// assert_eq!(first, Some(1));
+    /// ```
+    pub fn first(mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+
+    /// Return the first element matching the predicate
+    ///
+    /// Stops pulling from the input as soon as a match is found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let first = vec!["ok", "ERROR a", "ok", "ERROR b"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .first_where(|l| l.starts_with("ERROR"));
+    ///
+    /// assert_eq!(first, Some("ERROR a"));
+    /// ```
+    pub fn first_where<F>(mut self, f: F) -> Option<I::Item>
+    where
+        F: FnMut(&I::Item) -> bool,
+    {
+        self.iter.find(f)
+    }
+
+    /// Return the first element for which `f` returns `Some`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let version = vec!["name=lob", "version=0.1", "version=0.2"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .find_map(|l| l.strip_prefix("version="));
+    ///
+    /// assert_eq!(version, Some("0.1"));
+    /// ```
+    pub fn find_map<F, B>(mut self, f: F) -> Option<B>
+    where
+        F: FnMut(I::Item) -> Option<B>,
+    {
+        self.iter.find_map(f)
+    }
+
+    /// Return the index of every element matching the predicate
+    ///
+    /// Indices are zero-based; with line input, add one for line numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let idx = vec!["ok", "ERROR", "ok", "ERROR"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .positions(|l| l.contains("ERROR"));
+    ///
+    /// assert_eq!(idx, vec![1, 3]);
+    /// ```
+    pub fn positions<F>(self, mut f: F) -> Vec<usize>
+    where
+        F: FnMut(&I::Item) -> bool,
+    {
+        self.iter
+            .enumerate()
+            .filter_map(|(idx, item)| f(&item).then_some(idx))
+            .collect()
+    }
+
+    /// Get the last element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let last = (1..10).lob().last();
+    ///
+    /// assert_eq!(last, Some(9));
+    /// ```
+    pub fn last(self) -> Option<I::Item> {
+        self.iter.last()
+    }
+
+    /// Return the last element matching the predicate
+    ///
+    /// Consumes the whole input in a single pass, keeping only the most
+    /// recent match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let last = vec!["ok", "ERROR a", "ok", "ERROR b", "ok"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .last_where(|l| l.starts_with("ERROR"));
+    ///
+    /// assert_eq!(last, Some("ERROR b"));
+    /// ```
+    pub fn last_where<F>(self, mut f: F) -> Option<I::Item>
+    where
+        F: FnMut(&I::Item) -> bool,
+    {
+        self.iter.filter(|item| f(item)).last()
+    }
+
+    /// Get the first n elements and whether any elements were left over
+    ///
+    /// The flag is `true` only if the input had more than `n` elements, so a
+    /// caller can note that its output was truncated. At most one element
+    /// past the first `n` is pulled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let (items, truncated) = (1..10).lob().take_reporting(3);
+    ///
+    /// assert_eq!(items, vec![1, 2, 3]);
+    /// assert!(truncated);
+    /// ```
+    pub fn take_reporting(mut self, n: usize) -> (Vec<I::Item>, bool) {
+        let items: Vec<I::Item> = self.iter.by_ref().take(n).collect();
+        let truncated = self.iter.next().is_some();
+        (items, truncated)
+    }
+
+    /// Get the last n elements (like `tail -n`)
+    ///
+    /// Only n elements are buffered at a time, so the whole stream is never
+    /// collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let tail = (1..10).lob().take_last(3);
+    ///
+    /// assert_eq!(tail, vec![7, 8, 9]);
+    /// ```
+    pub fn take_last(self, n: usize) -> Vec<I::Item> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let mut buffer = VecDeque::with_capacity(n);
+        for item in self.iter {
+            if buffer.len() == n {
+                buffer.pop_front();
+            }
+            buffer.push_back(item);
+        }
+        buffer.into()
+    }
+
+    /// Reduce to a single value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let product = (1..=5).lob().reduce(|a, b| a * b);
+    ///
+    /// assert_eq!(product, Some(120));
     /// ```
     pub fn reduce<F>(self, f: F) -> Option<I::Item>
     where
@@ -577,6 +2419,164 @@ impl<I: Iterator> Lob<I> {
         self.iter.collect()
     }
 
+    /// Collect into a Vec of strings, converting each element with `to_string`
+    ///
+    /// Unlike `collect`, no type annotation is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let strings = (1..4).lob().collect_strings();
+    ///
+    /// assert_eq!(strings, vec!["1", "2", "3"]);
+    /// ```
+    pub fn collect_strings(self) -> Vec<String>
+    where
+        I::Item: ToString,
+    {
+        self.iter.map(|item| item.to_string()).collect()
+    }
+
+    /// Collect into a `HashSet`, dropping duplicates
+    ///
+    /// Unlike `collect`, no type annotation is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let set = vec!["b", "a", "b"].into_iter().lob().collect_set();
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.contains("a"));
+    /// ```
+    pub fn collect_set(self) -> HashSet<I::Item>
+    where
+        I::Item: Eq + Hash,
+    {
+        self.iter.collect()
+    }
+
+    /// Map each element with a fallible function, stopping at the first error
+    ///
+    /// In a lob expression the result is printed as a `Result`: debug output shows
+    /// `Ok([...])` or `Err(...)`, and JSON output shows `{"Ok":[...]}` or
+    /// `{"Err":...}`. JSON output requires a serializable error type, so convert
+    /// errors with `.map_err(|e| e.to_string())`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error produced by `f`; later elements are not visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let ok = vec!["1", "2"].into_iter().lob().try_map(|x| x.parse::<i32>());
+    /// assert_eq!(ok, Ok(vec![1, 2]));
+    ///
+    /// let err = vec!["1", "x"].into_iter().lob().try_map(|x| x.parse::<i32>());
+    /// assert!(err.is_err());
+    /// ```
+    pub fn try_map<F, B, E>(self, f: F) -> Result<Vec<B>, E>
+    where
+        F: FnMut(I::Item) -> Result<B, E>,
+    {
+        self.iter.map(f).collect()
+    }
+
+    /// Collect a stream of `Result`s into a collection, stopping at the first error
+    ///
+    /// The collection type usually needs naming, as in
+    /// `try_collect::<Vec<_>, _, _>()`. Output is printed as for `try_map`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `Err` in the stream; later elements are not pulled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let ok = vec!["1", "2"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .map(|x| x.parse::<i32>())
+    ///     .try_collect::<Vec<_>, _, _>();
+    /// assert_eq!(ok, Ok(vec![1, 2]));
+    ///
+    /// let err = vec!["1", "x"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .map(|x| x.parse::<i32>())
+    ///     .try_collect::<Vec<_>, _, _>();
+    /// assert!(err.is_err());
+    /// ```
+    pub fn try_collect<B, T, E>(self) -> Result<B, E>
+    where
+        I: Iterator<Item = Result<T, E>>,
+        B: FromIterator<T>,
+    {
+        self.iter.collect()
+    }
+
+    /// Partition a stream of `Result`s into successes and failures
+    ///
+    /// Both sides keep their input order. Useful for inspecting dirty data:
+    /// parse everything, then look at what failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// let (numbers, errors) = vec!["1", "x", "3"]
+    ///     .into_iter()
+    ///     .lob()
+    ///     .map(|s| s.parse::<i32>())
+    ///     .split_results();
+    ///
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn split_results<B, E>(self) -> (Vec<B>, Vec<E>)
+    where
+        I: Iterator<Item = Result<B, E>>,
+    {
+        let mut oks = Vec::new();
+        let mut errs = Vec::new();
+        for item in self.iter {
+            match item {
+                Ok(value) => oks.push(value),
+                Err(error) => errs.push(error),
+            }
+        }
+        (oks, errs)
+    }
+
+    /// Check whether the stream yields no elements
+    ///
+    /// Short-circuits after pulling at most one element, unlike `.count() == 0`.
+    /// This consumes the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_core::LobExt;
+    ///
+    /// assert!((0..0).lob().is_empty());
+    /// assert!(!(0..).lob().is_empty());
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    pub fn is_empty(mut self) -> bool {
+        self.iter.next().is_none()
+    }
+
     /// Check if any element matches a predicate
     ///
     /// # Examples
@@ -1,4 +1,4 @@
-//! Grouping iterators: `chunk`, `window`, `group_by`
+//! Grouping iterators: `chunk`, `chunk_while`, `chunk_on_change`, `batch_by_size`, `split_on`, `run_length_encode`, `window`, `window_step`, `group_by`, `group_by_bounded`, `group_by_ordered`
 
 #![allow(clippy::missing_const_for_fn)]
 
@@ -39,6 +39,242 @@ impl<I: Iterator> Iterator for ChunkIterator<I> {
     }
 }
 
+/// Iterator that groups consecutive elements while a pairwise predicate holds
+pub struct ChunkWhileIterator<I: Iterator, F> {
+    iter: I,
+    predicate: F,
+    pending: Option<I::Item>,
+}
+
+impl<I: Iterator, F> ChunkWhileIterator<I, F>
+where
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    pub fn new(iter: I, predicate: F) -> Self {
+        Self {
+            iter,
+            predicate,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for ChunkWhileIterator<I, F>
+where
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iter.next())?;
+        let mut chunk = vec![first];
+
+        for item in self.iter.by_ref() {
+            if (self.predicate)(&chunk[chunk.len() - 1], &item) {
+                chunk.push(item);
+            } else {
+                // Boundary found: hold the item for the next chunk
+                self.pending = Some(item);
+                break;
+            }
+        }
+
+        Some(chunk)
+    }
+}
+
+/// Iterator that starts a new chunk whenever the key of an element changes
+pub struct ChunkOnChangeIterator<I: Iterator, K, F> {
+    iter: I,
+    key_fn: F,
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I: Iterator, K, F> ChunkOnChangeIterator<I, K, F>
+where
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    pub fn new(iter: I, key_fn: F) -> Self {
+        Self {
+            iter,
+            key_fn,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator, K, F> Iterator for ChunkOnChangeIterator<I, K, F>
+where
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = if let Some(pending) = self.pending.take() {
+            pending
+        } else {
+            let item = self.iter.next()?;
+            ((self.key_fn)(&item), item)
+        };
+        let mut chunk = vec![first];
+
+        for item in self.iter.by_ref() {
+            let next_key = (self.key_fn)(&item);
+            if next_key == key {
+                chunk.push(item);
+            } else {
+                // Key changed: hold the item and its key for the next chunk
+                self.pending = Some((next_key, item));
+                break;
+            }
+        }
+
+        Some(chunk)
+    }
+}
+
+/// Iterator that packs consecutive elements into batches bounded by total size
+pub struct BatchBySizeIterator<I: Iterator, F> {
+    iter: I,
+    max_size: usize,
+    size_of: F,
+    pending: Option<(I::Item, usize)>,
+}
+
+impl<I: Iterator, F> BatchBySizeIterator<I, F>
+where
+    F: FnMut(&I::Item) -> usize,
+{
+    pub fn new(iter: I, max_size: usize, size_of: F) -> Self {
+        Self {
+            iter,
+            max_size,
+            size_of,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for BatchBySizeIterator<I, F>
+where
+    F: FnMut(&I::Item) -> usize,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::new();
+        let mut total: usize = 0;
+
+        loop {
+            let next = self.pending.take().or_else(|| {
+                let item = self.iter.next()?;
+                let size = (self.size_of)(&item);
+                Some((item, size))
+            });
+            let Some((item, size)) = next else { break };
+
+            // An oversized item still goes out, alone in its own batch
+            if !batch.is_empty() && total.saturating_add(size) > self.max_size {
+                self.pending = Some((item, size));
+                break;
+            }
+            batch.push(item);
+            total = total.saturating_add(size);
+        }
+
+        (!batch.is_empty()).then_some(batch)
+    }
+}
+
+/// Iterator that splits a stream into groups separated by delimiter elements
+pub struct SplitOnIterator<I: Iterator, F> {
+    iter: I,
+    is_delim: F,
+    seen_any: bool,
+    finished: bool,
+}
+
+impl<I: Iterator, F> SplitOnIterator<I, F>
+where
+    F: FnMut(&I::Item) -> bool,
+{
+    pub fn new(iter: I, is_delim: F) -> Self {
+        Self {
+            iter,
+            is_delim,
+            seen_any: false,
+            finished: false,
+        }
+    }
+}
+
+impl<I: Iterator, F> Iterator for SplitOnIterator<I, F>
+where
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut group = Vec::new();
+        for item in self.iter.by_ref() {
+            self.seen_any = true;
+            if (self.is_delim)(&item) {
+                return Some(group);
+            }
+            group.push(item);
+        }
+
+        // Emit the trailing group, unless the stream was empty
+        self.finished = true;
+        self.seen_any.then_some(group)
+    }
+}
+
+/// Iterator that yields `(value, run_length)` for runs of equal elements
+pub struct RunLengthIterator<I: Iterator> {
+    iter: I,
+    pending: Option<I::Item>,
+}
+
+impl<I: Iterator> RunLengthIterator<I> {
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RunLengthIterator<I>
+where
+    I::Item: PartialEq,
+{
+    type Item = (I::Item, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.pending.take().or_else(|| self.iter.next())?;
+        let mut run_length = 1;
+
+        for item in self.iter.by_ref() {
+            if item == value {
+                run_length += 1;
+            } else {
+                // Run ended: hold the item as the start of the next run
+                self.pending = Some(item);
+                break;
+            }
+        }
+
+        Some((value, run_length))
+    }
+}
+
 /// Iterator that creates sliding windows of size n
 pub struct WindowIterator<I: Iterator> {
     iter: I,
@@ -97,6 +333,58 @@ where
     }
 }
 
+/// Iterator over windows of a fixed size that advance by `step` elements
+pub struct WindowStepIterator<I: Iterator> {
+    iter: I,
+    window_size: usize,
+    step: usize,
+    buffer: VecDeque<I::Item>,
+    started: bool,
+}
+
+impl<I: Iterator> WindowStepIterator<I>
+where
+    I::Item: Clone,
+{
+    pub fn new(iter: I, window_size: usize, step: usize) -> Self {
+        assert!(window_size > 0, "window size must be greater than 0");
+        assert!(step > 0, "step must be greater than 0");
+        Self {
+            iter,
+            window_size,
+            step,
+            buffer: VecDeque::with_capacity(window_size),
+            started: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for WindowStepIterator<I>
+where
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.started {
+            if self.step <= self.buffer.len() {
+                self.buffer.drain(..self.step);
+            } else {
+                // The step jumps past the current window: skip the gap
+                let gap = self.step - self.buffer.len();
+                self.buffer.clear();
+                self.iter.nth(gap - 1)?;
+            }
+        }
+        self.started = true;
+
+        while self.buffer.len() < self.window_size {
+            self.buffer.push_back(self.iter.next()?);
+        }
+        Some(self.buffer.iter().cloned().collect())
+    }
+}
+
 /// Specialized `group_by` that returns all groups at once
 pub struct GroupByCollectIterator<I, K, F>
 where
@@ -151,3 +439,151 @@ where
         self.groups.as_mut().and_then(std::iter::Iterator::next)
     }
 }
+
+/// A group yielded by `group_by_bounded`; the overflow bucket is keyed `None`
+type BoundedGroup<K, T> = (Option<K>, Vec<T>);
+
+/// `group_by` variant that caps the number of distinct groups
+///
+/// Groups are keyed `Some(key)`. Once `max_groups` keys exist, items with new
+/// keys go to a single overflow bucket keyed `None`, which is yielded last.
+pub struct GroupByBoundedIterator<I, K, F>
+where
+    I: Iterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    groups: Option<std::vec::IntoIter<BoundedGroup<K, I::Item>>>,
+    iter: Option<I>,
+    key_fn: Option<F>,
+    max_groups: usize,
+}
+
+impl<I, K, F> GroupByBoundedIterator<I, K, F>
+where
+    I: Iterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    pub fn new(iter: I, key_fn: F, max_groups: usize) -> Self {
+        Self {
+            groups: None,
+            iter: Some(iter),
+            key_fn: Some(key_fn),
+            max_groups,
+        }
+    }
+}
+
+impl<I, K, F> Iterator for GroupByBoundedIterator<I, K, F>
+where
+    I: Iterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = BoundedGroup<K, I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Lazy initialization: collect groups on first call
+        if self.groups.is_none() {
+            let mut groups: HashMap<K, Vec<I::Item>> = HashMap::new();
+            let mut overflow: Vec<I::Item> = Vec::new();
+            let mut key_fn = self.key_fn.take().expect("key_fn should be Some");
+            let iter = self.iter.take().expect("iter should be Some");
+
+            for item in iter {
+                let key = key_fn(&item);
+                if let Some(group) = groups.get_mut(&key) {
+                    group.push(item);
+                } else if groups.len() < self.max_groups {
+                    groups.insert(key, vec![item]);
+                } else {
+                    if overflow.is_empty() {
+                        eprintln!(
+                            "warning: group_by_bounded reached {} groups; items with new keys go to the overflow group (key None)",
+                            self.max_groups
+                        );
+                    }
+                    overflow.push(item);
+                }
+            }
+
+            let mut all: Vec<BoundedGroup<K, I::Item>> =
+                groups.into_iter().map(|(k, v)| (Some(k), v)).collect();
+            if !overflow.is_empty() {
+                all.push((None, overflow));
+            }
+            self.groups = Some(all.into_iter());
+        }
+
+        self.groups.as_mut().and_then(std::iter::Iterator::next)
+    }
+}
+
+/// `group_by` variant that yields groups in the order their keys first appear
+pub struct GroupByOrderedIterator<I, K, F>
+where
+    I: Iterator,
+    K: Eq + Hash + Clone,
+    F: FnMut(&I::Item) -> K,
+{
+    groups: Option<std::vec::IntoIter<(K, Vec<I::Item>)>>,
+    iter: Option<I>,
+    key_fn: Option<F>,
+}
+
+impl<I, K, F> GroupByOrderedIterator<I, K, F>
+where
+    I: Iterator,
+    K: Eq + Hash + Clone,
+    F: FnMut(&I::Item) -> K,
+{
+    pub fn new(iter: I, key_fn: F) -> Self {
+        Self {
+            groups: None,
+            iter: Some(iter),
+            key_fn: Some(key_fn),
+        }
+    }
+}
+
+impl<I, K, F> Iterator for GroupByOrderedIterator<I, K, F>
+where
+    I: Iterator,
+    K: Eq + Hash + Clone,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Lazy initialization: collect groups on first call, remembering the
+        // order in which keys were first seen
+        if self.groups.is_none() {
+            let mut groups: HashMap<K, Vec<I::Item>> = HashMap::new();
+            let mut order: Vec<K> = Vec::new();
+            let mut key_fn = self.key_fn.take().expect("key_fn should be Some");
+            let iter = self.iter.take().expect("iter should be Some");
+
+            for item in iter {
+                let key = key_fn(&item);
+                if let Some(group) = groups.get_mut(&key) {
+                    group.push(item);
+                } else {
+                    order.push(key.clone());
+                    groups.insert(key, vec![item]);
+                }
+            }
+
+            let ordered: Vec<(K, Vec<I::Item>)> = order
+                .into_iter()
+                .map(|key| {
+                    let group = groups.remove(&key).unwrap_or_default();
+                    (key, group)
+                })
+                .collect();
+            self.groups = Some(ordered.into_iter());
+        }
+
+        self.groups.as_mut().and_then(std::iter::Iterator::next)
+    }
+}
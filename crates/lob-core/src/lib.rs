@@ -9,6 +9,10 @@
 mod fluent;
 mod grouping;
 mod joins;
+mod numeric;
+mod random;
+mod selection;
+mod transformation;
 
 pub use fluent::{Lob, LobExt};
 
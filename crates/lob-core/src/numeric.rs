@@ -0,0 +1,77 @@
+//! Numeric iterators: `rolling_mean`, `diff`
+
+use std::collections::VecDeque;
+
+/// Iterator that yields the mean of a sliding window of size n
+pub struct RollingMeanIterator<I: Iterator> {
+    iter: I,
+    window_size: usize,
+    buffer: VecDeque<f64>,
+    sum: f64,
+}
+
+impl<I: Iterator> RollingMeanIterator<I>
+where
+    I::Item: Into<f64>,
+{
+    pub fn new(iter: I, window_size: usize) -> Self {
+        assert!(window_size > 0, "window size must be greater than 0");
+        Self {
+            iter,
+            window_size,
+            buffer: VecDeque::with_capacity(window_size),
+            sum: 0.0,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RollingMeanIterator<I>
+where
+    I::Item: Into<f64>,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let value: f64 = self.iter.next()?.into();
+            self.buffer.push_back(value);
+            self.sum += value;
+
+            if self.buffer.len() > self.window_size {
+                self.sum -= self.buffer.pop_front().unwrap_or_default();
+            }
+
+            if self.buffer.len() == self.window_size {
+                return Some(self.sum / self.window_size as f64);
+            }
+        }
+    }
+}
+
+/// Iterator that yields the difference between each element and the previous one
+pub struct DiffIterator<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+}
+
+impl<I: Iterator> DiffIterator<I> {
+    pub fn new(iter: I) -> Self {
+        Self { iter, prev: None }
+    }
+}
+
+impl<I: Iterator> Iterator for DiffIterator<I>
+where
+    I::Item: Copy + std::ops::Sub<Output = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current = self.iter.next()?;
+            if let Some(prev) = self.prev.replace(current) {
+                return Some(current - prev);
+            }
+        }
+    }
+}
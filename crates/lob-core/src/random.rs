@@ -0,0 +1,72 @@
+//! Small seeded PRNG for the randomized operations
+//!
+//! `SplitMix64` is fast, has a 64-bit state, and is good enough for sampling
+//! and shuffling. It is not cryptographically secure.
+
+#![allow(clippy::missing_const_for_fn)]
+
+/// `SplitMix64` pseudo-random number generator
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index in `0..bound`
+    ///
+    /// Uses the multiply-shift reduction, whose bias is negligible for the
+    /// bounds a pipeline sees.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn below(&mut self, bound: usize) -> usize {
+        // The high half is < bound, so it always fits back into usize
+        let product = u128::from(self.next_u64()) * bound as u128;
+        (product >> 64) as usize
+    }
+
+    /// Uniform float in `[0, 1)`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill an f64 mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+/// An element tagged with its random key in a weighted reservoir
+///
+/// Ordered by key alone (with `total_cmp`), so a `BinaryHeap` can keep the
+/// smallest key on top.
+pub struct WeightedItem<T> {
+    pub key: f64,
+    pub item: T,
+}
+
+impl<T> PartialEq for WeightedItem<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key.total_cmp(&other.key).is_eq()
+    }
+}
+
+impl<T> Eq for WeightedItem<T> {}
+
+impl<T> PartialOrd for WeightedItem<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for WeightedItem<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key.total_cmp(&other.key)
+    }
+}
@@ -0,0 +1,156 @@
+//! Selection iterators: `dedup`, `dedup_by_key`, `dedup_by`, `skip_last`
+
+use std::collections::VecDeque;
+
+/// Iterator that drops elements equal to the previously emitted one
+pub struct DedupIterator<I: Iterator> {
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I: Iterator> DedupIterator<I> {
+    pub fn new(iter: I) -> Self {
+        Self { iter, last: None }
+    }
+}
+
+impl<I: Iterator> Iterator for DedupIterator<I>
+where
+    I::Item: PartialEq + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// Iterator that drops elements a closure deems equal to the last kept element
+pub struct DedupByIterator<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    iter: I,
+    same: F,
+    last: Option<I::Item>,
+}
+
+impl<I, F> DedupByIterator<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    pub fn new(iter: I, same: F) -> Self {
+        Self {
+            iter,
+            same,
+            last: None,
+        }
+    }
+}
+
+impl<I, F> Iterator for DedupByIterator<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            let duplicate = match &self.last {
+                Some(last) => (self.same)(last, &item),
+                None => false,
+            };
+            if !duplicate {
+                self.last = Some(item.clone());
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// Iterator that drops elements whose key equals the previous element's key
+pub struct DedupByKeyIterator<I, K, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+{
+    iter: I,
+    key_fn: F,
+    last_key: Option<K>,
+}
+
+impl<I, K, F> DedupByKeyIterator<I, K, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+{
+    pub fn new(iter: I, key_fn: F) -> Self {
+        Self {
+            iter,
+            key_fn,
+            last_key: None,
+        }
+    }
+}
+
+impl<I, K, F> Iterator for DedupByKeyIterator<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            let key = (self.key_fn)(&item);
+            if self.last_key.as_ref() != Some(&key) {
+                self.last_key = Some(key);
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// Iterator that omits the final n elements by staying n elements behind
+pub struct SkipLastIterator<I: Iterator> {
+    iter: I,
+    n: usize,
+    buffer: VecDeque<I::Item>,
+}
+
+impl<I: Iterator> SkipLastIterator<I> {
+    pub fn new(iter: I, n: usize) -> Self {
+        Self {
+            iter,
+            n,
+            buffer: VecDeque::with_capacity(n + 1),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for SkipLastIterator<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            self.buffer.push_back(item);
+            if self.buffer.len() > self.n {
+                return self.buffer.pop_front();
+            }
+        }
+    }
+}
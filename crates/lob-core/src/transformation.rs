@@ -0,0 +1,100 @@
+//! Transformation iterators: `intersperse`, `interleave`, `merge`
+
+use std::iter::{Fuse, Peekable};
+
+/// Iterator that merges two sorted iterators into one sorted stream
+pub struct MergeIterator<I: Iterator, J: Iterator<Item = I::Item>> {
+    left: Peekable<I>,
+    right: Peekable<J>,
+}
+
+impl<I: Iterator, J: Iterator<Item = I::Item>> MergeIterator<I, J> {
+    pub fn new(left: I, right: J) -> Self {
+        Self {
+            left: left.peekable(),
+            right: right.peekable(),
+        }
+    }
+}
+
+impl<I: Iterator, J: Iterator<Item = I::Item>> Iterator for MergeIterator<I, J>
+where
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.left.peek(), self.right.peek()) {
+            // Prefer the left side on ties so the merge is stable
+            (Some(l), Some(r)) if r < l => self.right.next(),
+            (Some(_), _) => self.left.next(),
+            (None, _) => self.right.next(),
+        }
+    }
+}
+
+/// Iterator that alternates between two iterators, then drains the longer one
+pub struct InterleaveIterator<I, J> {
+    left: Fuse<I>,
+    right: Fuse<J>,
+    right_next: bool,
+}
+
+impl<I: Iterator, J: Iterator<Item = I::Item>> InterleaveIterator<I, J> {
+    pub fn new(left: I, right: J) -> Self {
+        Self {
+            left: left.fuse(),
+            right: right.fuse(),
+            right_next: false,
+        }
+    }
+}
+
+impl<I: Iterator, J: Iterator<Item = I::Item>> Iterator for InterleaveIterator<I, J> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = if self.right_next {
+            self.right.next().or_else(|| self.left.next())
+        } else {
+            self.left.next().or_else(|| self.right.next())
+        };
+        self.right_next = !self.right_next;
+        item
+    }
+}
+
+/// Iterator that inserts a separator between consecutive elements
+pub struct IntersperseIterator<I: Iterator> {
+    iter: Peekable<I>,
+    separator: I::Item,
+    needs_separator: bool,
+}
+
+impl<I: Iterator> IntersperseIterator<I> {
+    pub fn new(iter: I, separator: I::Item) -> Self {
+        Self {
+            iter: iter.peekable(),
+            separator,
+            needs_separator: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for IntersperseIterator<I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.needs_separator && self.iter.peek().is_some() {
+            self.needs_separator = false;
+            return Some(self.separator.clone());
+        }
+
+        let item = self.iter.next()?;
+        self.needs_separator = true;
+        Some(item)
+    }
+}
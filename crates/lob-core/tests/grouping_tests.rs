@@ -1,6 +1,6 @@
 //! Comprehensive tests for grouping operations
 
-use lob_core::LobExt;
+use lob_core::{HashMap, LobExt};
 
 #[test]
 fn chunk_basic() {
@@ -32,6 +32,27 @@ fn chunk_empty() {
     assert!(result.is_empty());
 }
 
+#[test]
+fn chunk_exact_drops_short_tail() {
+    let exact: Vec<_> = (0..7).lob().chunk_exact(3).collect();
+    let all: Vec<_> = (0..7).lob().chunk(3).collect();
+    assert_eq!(exact, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+    assert_eq!(exact, all[..2]);
+}
+
+#[test]
+fn chunk_exact_fit_matches_chunk() {
+    let exact: Vec<_> = (0..6).lob().chunk_exact(2).collect();
+    let all: Vec<_> = (0..6).lob().chunk(2).collect();
+    assert_eq!(exact, all);
+}
+
+#[test]
+fn chunk_exact_too_short() {
+    let result: Vec<_> = (0..3).lob().chunk_exact(10).collect();
+    assert!(result.is_empty());
+}
+
 #[test]
 fn window_basic() {
     let result: Vec<_> = (1..=5).lob().window(3).collect();
@@ -68,6 +89,51 @@ fn window_exact_size() {
     assert_eq!(result, vec![vec![1, 2, 3]]);
 }
 
+#[test]
+fn window_step_overlapping() {
+    let result: Vec<_> = (1..=6).lob().window_step(4, 2).collect();
+    assert_eq!(result, vec![vec![1, 2, 3, 4], vec![3, 4, 5, 6]]);
+}
+
+#[test]
+fn window_step_one_matches_window() {
+    let stepped: Vec<_> = (1..=5).lob().window_step(3, 1).collect();
+    let sliding: Vec<_> = (1..=5).lob().window(3).collect();
+    assert_eq!(stepped, sliding);
+}
+
+#[test]
+fn window_step_tiling_matches_chunk_exact() {
+    let stepped: Vec<_> = (1..=8).lob().window_step(3, 3).collect();
+    let chunked: Vec<_> = (1..=8).lob().chunk_exact(3).collect();
+    assert_eq!(stepped, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    assert_eq!(stepped, chunked);
+}
+
+#[test]
+fn window_step_with_gaps() {
+    let result: Vec<_> = (1..=10).lob().window_step(2, 4).collect();
+    assert_eq!(result, vec![vec![1, 2], vec![5, 6], vec![9, 10]]);
+}
+
+#[test]
+fn window_step_gap_past_end() {
+    let result: Vec<_> = (1..=7).lob().window_step(2, 4).collect();
+    assert_eq!(result, vec![vec![1, 2], vec![5, 6]]);
+}
+
+#[test]
+fn window_step_too_small() {
+    let result: Vec<Vec<i32>> = vec![1, 2].into_iter().lob().window_step(3, 1).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "step must be greater than 0")]
+fn window_step_zero_step_panics() {
+    let _ = (1..=3).lob().window_step(2, 0);
+}
+
 #[test]
 fn group_by_basic() {
     let data = vec![1, 2, 3, 4, 5, 6];
@@ -103,6 +169,127 @@ fn group_by_strings() {
     assert_eq!(groups[2].1, vec!["cherry"]);
 }
 
+#[test]
+fn group_by_bounded_within_cap_matches_group_by() {
+    let data = vec!["x1", "y1", "x2", "z1", "y2"];
+    let key = |s: &&str| s[..1].to_string();
+
+    let mut bounded: Vec<_> = data
+        .clone()
+        .into_iter()
+        .lob()
+        .group_by_bounded(key, 3)
+        .collect();
+    let mut unbounded: Vec<_> = data
+        .into_iter()
+        .lob()
+        .group_by(key)
+        .map(|(k, v)| (Some(k), v))
+        .collect();
+    bounded.sort();
+    unbounded.sort();
+
+    assert_eq!(bounded, unbounded);
+}
+
+#[test]
+fn group_by_bounded_overflow_captures_new_keys() {
+    let data = vec!["x1", "y1", "z1", "x2", "w1", "z2", "y2"];
+    let mut groups: Vec<_> = data
+        .into_iter()
+        .lob()
+        .group_by_bounded(|s| s[..1].to_string(), 2)
+        .collect();
+
+    // Keys past the cap land in the overflow bucket, which comes last
+    assert_eq!(groups.pop(), Some((None, vec!["z1", "w1", "z2"])));
+    groups.sort();
+    assert_eq!(
+        groups,
+        vec![
+            (Some("x".to_string()), vec!["x1", "x2"]),
+            (Some("y".to_string()), vec!["y1", "y2"]),
+        ]
+    );
+}
+
+#[test]
+fn group_by_bounded_zero_cap_sends_everything_to_overflow() {
+    let groups: Vec<_> = vec![1, 2, 3]
+        .into_iter()
+        .lob()
+        .group_by_bounded(|x| if x % 2 == 0 { "even" } else { "odd" }, 0)
+        .collect();
+
+    assert_eq!(groups, vec![(None, vec![1, 2, 3])]);
+}
+
+#[test]
+fn group_by_bounded_overflow_does_not_collide_with_real_keys() {
+    let groups: Vec<_> = vec!["__overflow", "a", "b"]
+        .into_iter()
+        .lob()
+        .group_by_bounded(|s| s.to_string(), 1)
+        .collect();
+
+    assert_eq!(
+        groups,
+        vec![
+            (Some("__overflow".to_string()), vec!["__overflow"]),
+            (None, vec!["a", "b"]),
+        ]
+    );
+}
+
+#[test]
+fn group_by_bounded_accepts_non_string_keys() {
+    let groups: Vec<_> = vec![1, 2, 3, 4]
+        .into_iter()
+        .lob()
+        .group_by_bounded(|x| x % 3, 1)
+        .collect();
+
+    assert_eq!(groups, vec![(Some(1), vec![1, 4]), (None, vec![2, 3])]);
+}
+
+#[test]
+fn group_by_ordered_first_seen_order() {
+    let data = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+    let groups: Vec<_> = data.into_iter().lob().group_by_ordered(|x| x % 3).collect();
+
+    assert_eq!(
+        groups,
+        vec![
+            (0, vec![3, 9, 6, 3]),
+            (1, vec![1, 4, 1]),
+            (2, vec![5, 2, 5])
+        ]
+    );
+}
+
+#[test]
+fn group_by_ordered_string_keys() {
+    let data = vec!["cherry", "apple", "cranberry", "banana", "avocado"];
+    let keys: Vec<_> = data
+        .into_iter()
+        .lob()
+        .group_by_ordered(|s| s[..1].to_string())
+        .map(|(k, _)| k)
+        .collect();
+
+    assert_eq!(keys, vec!["c", "a", "b"]);
+}
+
+#[test]
+fn group_by_ordered_empty() {
+    let groups: Vec<(i32, Vec<i32>)> = Vec::new()
+        .into_iter()
+        .lob()
+        .group_by_ordered(|x| *x)
+        .collect();
+    assert!(groups.is_empty());
+}
+
 #[test]
 fn group_by_single_group() {
     let data = vec![1, 1, 1, 1];
@@ -178,3 +365,310 @@ fn window_iterator_size_hint() {
     let (lower, _upper) = windows.size_hint();
     assert_eq!(lower, 0);
 }
+
+#[test]
+fn batch_by_size_exact_fit() {
+    let result: Vec<_> = vec![2, 3, 5, 1, 4]
+        .into_iter()
+        .lob()
+        .batch_by_size(5, |&n| n)
+        .collect();
+    assert_eq!(result, vec![vec![2, 3], vec![5], vec![1, 4]]);
+}
+
+#[test]
+fn batch_by_size_oversized_item_alone() {
+    let result: Vec<_> = vec![1, 10, 1, 1]
+        .into_iter()
+        .lob()
+        .batch_by_size(3, |&n| n)
+        .collect();
+    assert_eq!(result, vec![vec![1], vec![10], vec![1, 1]]);
+}
+
+#[test]
+fn batch_by_size_packs_strings_by_length() {
+    let result: Vec<_> = vec!["ab", "cd", "ef", "gh", "i"]
+        .into_iter()
+        .lob()
+        .batch_by_size(5, |s| s.len())
+        .collect();
+    assert_eq!(result, vec![vec!["ab", "cd"], vec!["ef", "gh", "i"]]);
+}
+
+#[test]
+fn batch_by_size_empty() {
+    let result: Vec<Vec<String>> = Vec::new()
+        .into_iter()
+        .lob()
+        .batch_by_size(10, String::len)
+        .collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn chunk_while_monotonic_runs() {
+    let result: Vec<_> = vec![1, 2, 3, 5, 4, 2, 6, 7]
+        .into_iter()
+        .lob()
+        .chunk_while(|prev, cur| cur > prev)
+        .collect();
+    assert_eq!(result, vec![vec![1, 2, 3, 5], vec![4], vec![2, 6, 7]]);
+}
+
+#[test]
+fn chunk_while_ascending_then_descending() {
+    let result: Vec<_> = vec![1, 3, 5, 4, 2, 0]
+        .into_iter()
+        .lob()
+        .chunk_while(|prev, cur| cur >= prev)
+        .collect();
+    assert_eq!(result, vec![vec![1, 3, 5], vec![4], vec![2], vec![0]]);
+}
+
+#[test]
+fn chunk_while_always_true() {
+    let result: Vec<_> = (0..4).lob().chunk_while(|_, _| true).collect();
+    assert_eq!(result, vec![vec![0, 1, 2, 3]]);
+}
+
+#[test]
+fn chunk_while_empty() {
+    let result: Vec<Vec<i32>> = vec![].into_iter().lob().chunk_while(|a, b| a < b).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn chunk_on_change_sorted_rows_by_category() {
+    let rows = vec![
+        ("fruit", "apple"),
+        ("fruit", "pear"),
+        ("nut", "almond"),
+        ("veg", "kale"),
+        ("veg", "leek"),
+        ("veg", "okra"),
+    ];
+    let result: Vec<Vec<&str>> = rows
+        .into_iter()
+        .lob()
+        .chunk_on_change(|row| row.0)
+        .map(|chunk| chunk.into_iter().map(|row| row.1).collect())
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            vec!["apple", "pear"],
+            vec!["almond"],
+            vec!["kale", "leek", "okra"]
+        ]
+    );
+}
+
+#[test]
+fn chunk_on_change_only_groups_adjacent_keys() {
+    let result: Vec<_> = vec![1, 1, 2, 1, 1]
+        .into_iter()
+        .lob()
+        .chunk_on_change(|x| *x)
+        .collect();
+    assert_eq!(result, vec![vec![1, 1], vec![2], vec![1, 1]]);
+}
+
+#[test]
+fn chunk_on_change_calls_key_fn_once_per_item() {
+    let mut calls = 0;
+    let result: Vec<_> = (0..6)
+        .lob()
+        .chunk_on_change(|x| {
+            calls += 1;
+            x / 2
+        })
+        .collect();
+    assert_eq!(result, vec![vec![0, 1], vec![2, 3], vec![4, 5]]);
+    assert_eq!(calls, 6);
+}
+
+#[test]
+fn chunk_on_change_empty() {
+    let result: Vec<Vec<i32>> = vec![].into_iter().lob().chunk_on_change(|x| *x).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn split_on_basic() {
+    let result: Vec<_> = vec![1, 2, 0, 3, 0, 4, 5]
+        .into_iter()
+        .lob()
+        .split_on(|x| *x == 0)
+        .collect();
+    assert_eq!(result, vec![vec![1, 2], vec![3], vec![4, 5]]);
+}
+
+#[test]
+fn split_on_leading_and_trailing_delimiters() {
+    let result: Vec<_> = vec![0, 1, 2, 0]
+        .into_iter()
+        .lob()
+        .split_on(|x| *x == 0)
+        .collect();
+    assert_eq!(result, vec![vec![], vec![1, 2], vec![]]);
+}
+
+#[test]
+fn split_on_consecutive_delimiters() {
+    let result: Vec<_> = vec![1, 0, 0, 2]
+        .into_iter()
+        .lob()
+        .split_on(|x| *x == 0)
+        .collect();
+    assert_eq!(result, vec![vec![1], vec![], vec![2]]);
+}
+
+#[test]
+fn split_on_no_delimiters() {
+    let result: Vec<_> = (1..4).lob().split_on(|x| *x == 0).collect();
+    assert_eq!(result, vec![vec![1, 2, 3]]);
+}
+
+#[test]
+fn split_on_empty() {
+    let result: Vec<Vec<i32>> = vec![].into_iter().lob().split_on(|x| *x == 0).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn run_length_encode_basic() {
+    let result: Vec<_> = vec!['a', 'a', 'b', 'a', 'c', 'c', 'c']
+        .into_iter()
+        .lob()
+        .run_length_encode()
+        .collect();
+    assert_eq!(result, vec![('a', 2), ('b', 1), ('a', 1), ('c', 3)]);
+}
+
+#[test]
+fn run_length_encode_all_same() {
+    let result: Vec<_> = vec![7, 7, 7, 7]
+        .into_iter()
+        .lob()
+        .run_length_encode()
+        .collect();
+    assert_eq!(result, vec![(7, 4)]);
+}
+
+#[test]
+fn run_length_encode_alternating() {
+    let result: Vec<_> = vec![1, 2, 1, 2]
+        .into_iter()
+        .lob()
+        .run_length_encode()
+        .collect();
+    assert_eq!(result, vec![(1, 1), (2, 1), (1, 1), (2, 1)]);
+}
+
+#[test]
+fn run_length_encode_empty() {
+    let result: Vec<(i32, usize)> = vec![].into_iter().lob().run_length_encode().collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn group_by_agg_matches_group_by_then_fold() {
+    let data = vec![("nyc", 31), ("sf", 25), ("nyc", 42), ("la", 19), ("sf", 38)];
+
+    let aggregated = data.clone().into_iter().lob().group_by_agg(
+        |(city, _)| *city,
+        || 0,
+        |max, (_, age)| max.max(age),
+    );
+
+    let manual: HashMap<_, _> = data
+        .into_iter()
+        .lob()
+        .group_by(|(city, _)| *city)
+        .map(|(city, rows)| (city, rows.into_iter().fold(0, |max, (_, age)| max.max(age))))
+        .collect();
+
+    assert_eq!(aggregated, manual);
+    assert_eq!(aggregated["nyc"], 42);
+}
+
+#[test]
+fn group_by_agg_sum_and_count() {
+    let totals = (1..=6)
+        .lob()
+        .group_by_agg(|x| x % 2, || (0, 0), |(sum, n), x| (sum + x, n + 1));
+
+    assert_eq!(totals.len(), 2);
+    assert_eq!(totals[&0], (12, 3));
+    assert_eq!(totals[&1], (9, 3));
+}
+
+#[test]
+fn group_by_agg_empty() {
+    let totals =
+        Vec::<i32>::new()
+            .into_iter()
+            .lob()
+            .group_by_agg(|x| x % 2, || 0, |acc, x| acc + x);
+    assert!(totals.is_empty());
+}
+
+#[test]
+fn partition_by_modulo_three() {
+    let buckets = (0..10).lob().partition_by(|x| x % 3);
+    assert_eq!(buckets.len(), 3);
+    assert_eq!(buckets[&0], vec![0, 3, 6, 9]);
+    assert_eq!(buckets[&1], vec![1, 4, 7]);
+    assert_eq!(buckets[&2], vec![2, 5, 8]);
+}
+
+#[test]
+fn partition_by_matches_group_by() {
+    let buckets = (0..10).lob().partition_by(|x| x % 3);
+    let grouped: HashMap<_, _> = (0..10).lob().group_by(|x| x % 3).collect();
+    assert_eq!(buckets, grouped);
+}
+
+#[test]
+fn partition_by_empty() {
+    let buckets = Vec::<i32>::new().into_iter().lob().partition_by(|x| x % 3);
+    assert!(buckets.is_empty());
+}
+
+#[test]
+fn max_by_group_per_key() {
+    let latencies = vec![
+        ("/users", 120.0_f64),
+        ("/orders", 40.5),
+        ("/users", 310.2),
+        ("/orders", 95.0),
+        ("/users", 200.0),
+    ];
+    let slowest = latencies
+        .into_iter()
+        .lob()
+        .max_by_group(|(path, _)| *path, |(_, ms)| *ms);
+
+    assert_eq!(slowest.len(), 2);
+    assert!((slowest["/users"] - 310.2).abs() < f64::EPSILON);
+    assert!((slowest["/orders"] - 95.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn max_by_group_single_member_groups() {
+    let maxima = vec![("a", 1), ("b", 2), ("c", 3)]
+        .into_iter()
+        .lob()
+        .max_by_group(|(k, _)| *k, |(_, v)| *v);
+    assert_eq!(maxima, HashMap::from([("a", 1), ("b", 2), ("c", 3)]));
+}
+
+#[test]
+fn max_by_group_empty() {
+    let maxima = Vec::<(i32, i32)>::new()
+        .into_iter()
+        .lob()
+        .max_by_group(|(k, _)| *k, |(_, v)| *v);
+    assert!(maxima.is_empty());
+}
@@ -0,0 +1,208 @@
+//! Comprehensive tests for numeric operations
+
+use lob_core::LobExt;
+
+#[test]
+fn ewma_alpha_one_is_identity() {
+    let data = vec![3.0, -1.0, 7.5, 2.0];
+    let result: Vec<_> = data.clone().into_iter().lob().ewma(1.0).collect();
+    assert_eq!(result, data);
+}
+
+#[test]
+fn ewma_known_sequence() {
+    let result: Vec<_> = vec![0, 10, 10, 10].into_iter().lob().ewma(0.2).collect();
+    let expected = [0.0, 2.0, 3.6, 4.88];
+    assert_eq!(result.len(), expected.len());
+    for (got, want) in result.iter().zip(expected) {
+        assert!((got - want).abs() < 1e-9, "{} != {}", got, want);
+    }
+}
+
+#[test]
+fn ewma_empty() {
+    let result: Vec<f64> = Vec::<f64>::new().into_iter().lob().ewma(0.5).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "alpha must be in (0, 1]")]
+fn ewma_rejects_zero_alpha() {
+    let _ = (0..3).lob().map(f64::from).ewma(0.0);
+}
+
+#[test]
+#[should_panic(expected = "alpha must be in (0, 1]")]
+fn ewma_rejects_alpha_above_one() {
+    let _ = (0..3).lob().map(f64::from).ewma(1.5);
+}
+
+#[test]
+fn rolling_mean_window_one() {
+    let result: Vec<_> = vec![1.0, 5.0, 3.0]
+        .into_iter()
+        .lob()
+        .rolling_mean(1)
+        .collect();
+    assert_eq!(result, vec![1.0, 5.0, 3.0]);
+}
+
+#[test]
+fn rolling_mean_window_two() {
+    let result: Vec<_> = vec![2, 4, 6, 8].into_iter().lob().rolling_mean(2).collect();
+    assert_eq!(result, vec![3.0, 5.0, 7.0]);
+}
+
+#[test]
+fn rolling_mean_window_three() {
+    let result: Vec<_> = vec![1, 2, 3, 4, 5]
+        .into_iter()
+        .lob()
+        .rolling_mean(3)
+        .collect();
+    assert_eq!(result, vec![2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn rolling_mean_larger_than_input() {
+    let result: Vec<_> = vec![1, 2].into_iter().lob().rolling_mean(5).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn rolling_mean_empty() {
+    let result: Vec<_> = Vec::<f64>::new()
+        .into_iter()
+        .lob()
+        .rolling_mean(2)
+        .collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "window size must be greater than 0")]
+fn rolling_mean_zero_window() {
+    let _ = vec![1, 2].into_iter().lob().rolling_mean(0);
+}
+
+#[test]
+fn cumulative_sum_integers() {
+    let result: Vec<_> = vec![1, 2, 3, 4]
+        .into_iter()
+        .lob()
+        .cumulative_sum()
+        .collect();
+    assert_eq!(result, vec![1, 3, 6, 10]);
+}
+
+#[test]
+fn cumulative_sum_negative() {
+    let result: Vec<_> = vec![5, -2, -3].into_iter().lob().cumulative_sum().collect();
+    assert_eq!(result, vec![5, 3, 0]);
+}
+
+#[test]
+fn cumulative_sum_floats() {
+    let result: Vec<_> = vec![0.5, 0.25].into_iter().lob().cumulative_sum().collect();
+    assert_eq!(result, vec![0.5, 0.75]);
+}
+
+#[test]
+fn cumulative_sum_empty() {
+    let result: Vec<i32> = vec![].into_iter().lob().cumulative_sum().collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn diff_basic() {
+    let result: Vec<_> = vec![1, 4, 9, 16].into_iter().lob().diff().collect();
+    assert_eq!(result, vec![3, 5, 7]);
+}
+
+#[test]
+fn diff_negative_deltas() {
+    let result: Vec<_> = vec![10, 7, 7, 8].into_iter().lob().diff().collect();
+    assert_eq!(result, vec![-3, 0, 1]);
+}
+
+#[test]
+fn diff_single() {
+    let result: Vec<_> = vec![5].into_iter().lob().diff().collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn diff_empty() {
+    let result: Vec<i32> = vec![].into_iter().lob().diff().collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn clamp_below_within_above() {
+    let result: Vec<_> = vec![-3, 0, 5, 10, 99]
+        .into_iter()
+        .lob()
+        .clamp(0, 10)
+        .collect();
+    assert_eq!(result, vec![0, 0, 5, 10, 10]);
+}
+
+#[test]
+fn clamp_floats() {
+    let result: Vec<f64> = vec![0.5, 1.5, 2.5]
+        .into_iter()
+        .lob()
+        .clamp(1.0, 2.0)
+        .collect();
+    assert_eq!(result.len(), 3);
+    assert!((result[0] - 1.0).abs() < f64::EPSILON);
+    assert!((result[1] - 1.5).abs() < f64::EPSILON);
+    assert!((result[2] - 2.0).abs() < f64::EPSILON);
+}
+
+#[test]
+#[should_panic(expected = "clamp requires lo <= hi")]
+fn clamp_inverted_range_panics() {
+    let _ = (0..3).lob().clamp(5, 1);
+}
+
+#[test]
+fn cumulative_max_non_monotonic() {
+    let result: Vec<_> = vec![1, 3, 2, 5, 4]
+        .into_iter()
+        .lob()
+        .cumulative_max()
+        .collect();
+    assert_eq!(result, vec![1, 3, 3, 5, 5]);
+}
+
+#[test]
+fn cumulative_max_monotonic() {
+    let increasing: Vec<_> = (1..5).lob().cumulative_max().collect();
+    assert_eq!(increasing, vec![1, 2, 3, 4]);
+
+    let decreasing: Vec<_> = (1..5).rev().lob().cumulative_max().collect();
+    assert_eq!(decreasing, vec![4, 4, 4, 4]);
+}
+
+#[test]
+fn cumulative_min_non_monotonic() {
+    let result: Vec<_> = vec![5, 2, 4, 1, 3]
+        .into_iter()
+        .lob()
+        .cumulative_min()
+        .collect();
+    assert_eq!(result, vec![5, 2, 2, 1, 1]);
+}
+
+#[test]
+fn cumulative_min_monotonic() {
+    let increasing: Vec<_> = (1..5).lob().cumulative_min().collect();
+    assert_eq!(increasing, vec![1, 1, 1, 1]);
+}
+
+#[test]
+fn cumulative_max_empty() {
+    let result: Vec<i32> = Vec::new().into_iter().lob().cumulative_max().collect();
+    assert!(result.is_empty());
+}
@@ -1,6 +1,6 @@
 //! Comprehensive tests for selection operations
 
-use lob_core::LobExt;
+use lob_core::{HashMap, LobExt};
 
 #[test]
 fn filter_basic() {
@@ -63,6 +63,59 @@ fn skip_zero() {
     assert_eq!(result, vec![0, 1, 2]);
 }
 
+#[test]
+fn skip_every_one_drops_all() {
+    let result: Vec<i32> = (0..5).lob().skip_every(1).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn skip_every_two_keeps_odd_positions() {
+    let result: Vec<_> = vec!["a", "b", "c", "d", "e"]
+        .into_iter()
+        .lob()
+        .skip_every(2)
+        .collect();
+    assert_eq!(result, vec!["a", "c", "e"]);
+}
+
+#[test]
+fn skip_every_counts_from_one() {
+    // Dropped elements sit at 1-based positions 4 and 8, whatever their values
+    let result: Vec<_> = (10..20).lob().skip_every(4).collect();
+    assert_eq!(result, vec![10, 11, 12, 14, 15, 16, 18, 19]);
+}
+
+#[test]
+#[should_panic(expected = "greater than 0")]
+fn skip_every_zero_panics() {
+    let _ = (0..3).lob().skip_every(0);
+}
+
+#[test]
+fn skip_last_basic() {
+    let result: Vec<_> = (0..5).lob().skip_last(2).collect();
+    assert_eq!(result, vec![0, 1, 2]);
+}
+
+#[test]
+fn skip_last_zero() {
+    let result: Vec<_> = (0..3).lob().skip_last(0).collect();
+    assert_eq!(result, vec![0, 1, 2]);
+}
+
+#[test]
+fn skip_last_equal_to_length() {
+    let result: Vec<_> = (0..3).lob().skip_last(3).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn skip_last_more_than_available() {
+    let result: Vec<_> = (0..3).lob().skip_last(10).collect();
+    assert!(result.is_empty());
+}
+
 #[test]
 fn take_while_basic() {
     let result: Vec<_> = vec![1, 2, 3, 4, 1, 2]
@@ -139,6 +192,107 @@ fn unique_strings() {
     assert_eq!(result, vec!["a", "b", "c"]);
 }
 
+#[test]
+fn dedup_basic() {
+    let result: Vec<_> = vec![1, 1, 2, 1, 1].into_iter().lob().dedup().collect();
+    assert_eq!(result, vec![1, 2, 1]);
+}
+
+#[test]
+fn dedup_differs_from_unique() {
+    let data = vec!["a", "a", "b", "a", "b", "b"];
+    let deduped: Vec<_> = data.clone().into_iter().lob().dedup().collect();
+    let uniques: Vec<_> = data.into_iter().lob().unique().collect();
+    assert_eq!(deduped, vec!["a", "b", "a", "b"]);
+    assert_eq!(uniques, vec!["a", "b"]);
+}
+
+#[test]
+fn dedup_no_duplicates() {
+    let result: Vec<_> = (1..5).lob().dedup().collect();
+    assert_eq!(result, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn dedup_empty() {
+    let result: Vec<i32> = vec![].into_iter().lob().dedup().collect();
+    assert!(result.is_empty());
+}
+
+fn row(city: &str, name: &str) -> HashMap<String, String> {
+    let mut row = HashMap::new();
+    row.insert("city".to_string(), city.to_string());
+    row.insert("name".to_string(), name.to_string());
+    row
+}
+
+#[test]
+fn dedup_by_case_insensitive() {
+    let result: Vec<_> = vec!["Apple", "APPLE", "apple", "Pear", "pear", "apple"]
+        .into_iter()
+        .lob()
+        .dedup_by(|a, b| a.to_lowercase() == b.to_lowercase())
+        .collect();
+    assert_eq!(result, vec!["Apple", "Pear", "apple"]);
+}
+
+#[test]
+fn dedup_by_compares_with_first_of_run() {
+    // Each value is within 1 of its neighbour, but the run is measured from 10
+    let result: Vec<_> = vec![10, 11, 12, 13]
+        .into_iter()
+        .lob()
+        .dedup_by(|kept: &i32, next| (kept - next).abs() <= 1)
+        .collect();
+    assert_eq!(result, vec![10, 12]);
+}
+
+#[test]
+fn dedup_by_empty() {
+    let result: Vec<String> = Vec::new()
+        .into_iter()
+        .lob()
+        .dedup_by(|a: &String, b| a.eq_ignore_ascii_case(b))
+        .collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn dedup_by_key_rows() {
+    let rows = vec![
+        row("NYC", "Alice"),
+        row("NYC", "Bob"),
+        row("LA", "Carol"),
+        row("LA", "Dave"),
+        row("SF", "Eve"),
+    ];
+    let result: Vec<_> = rows
+        .into_iter()
+        .lob()
+        .dedup_by_key(|r| r["city"].clone())
+        .map(|r| r["name"].clone())
+        .collect();
+    assert_eq!(result, vec!["Alice", "Carol", "Eve"]);
+}
+
+#[test]
+fn dedup_by_key_preserves_non_adjacent() {
+    let rows = vec![row("NYC", "Alice"), row("LA", "Bob"), row("NYC", "Carol")];
+    let result: Vec<_> = rows
+        .into_iter()
+        .lob()
+        .dedup_by_key(|r| r["city"].clone())
+        .map(|r| r["name"].clone())
+        .collect();
+    assert_eq!(result, vec!["Alice", "Bob", "Carol"]);
+}
+
+#[test]
+fn dedup_by_key_empty() {
+    let result: Vec<i32> = vec![].into_iter().lob().dedup_by_key(|x| x % 2).collect();
+    assert!(result.is_empty());
+}
+
 #[test]
 fn chained_selection() {
     let result: Vec<_> = (0..20)
@@ -27,6 +27,90 @@ fn count_after_filter() {
     assert_eq!(count, 5);
 }
 
+#[test]
+fn count_distinct_duplicate_heavy() {
+    let count = (0..1000).lob().map(|x| x % 7).count_distinct();
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn count_distinct_empty() {
+    let count = Vec::<String>::new().into_iter().lob().count_distinct();
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn count_distinct_matches_unique_count() {
+    let data = vec!["x", "y", "x", "z", "y", "x"];
+    let expected = data.clone().into_iter().lob().unique().count();
+    assert_eq!(data.into_iter().lob().count_distinct(), expected);
+}
+
+#[test]
+fn sample_seeded_is_deterministic() {
+    let a = (0..10_000).lob().sample_seeded(5, 7);
+    let b = (0..10_000).lob().sample_seeded(5, 7);
+    assert_eq!(a, b);
+    assert_eq!(a.len(), 5);
+    assert_ne!(a, (0..10_000).lob().sample_seeded(5, 8));
+}
+
+#[test]
+fn sample_seeded_short_input_returned_whole() {
+    let result = (0..3).lob().sample_seeded(10, 1);
+    assert_eq!(result, vec![0, 1, 2]);
+}
+
+#[test]
+fn sample_seeded_picks_distinct_input_items() {
+    let result = (0..100).lob().sample_seeded(10, 3);
+    assert_eq!(
+        result
+            .iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len(),
+        10
+    );
+    assert!(result.iter().all(|x| (0..100).contains(x)));
+}
+
+#[test]
+fn sample_seeded_zero() {
+    assert!((0..10).lob().sample_seeded(0, 1).is_empty());
+}
+
+#[test]
+fn sample_weighted_prefers_heavy_items() {
+    // Item 0 carries half the total weight; uniform sampling would pick it 1% of the time
+    let picked_heavy = (0..1000u64)
+        .filter(|&seed| {
+            let pick =
+                (0..100)
+                    .lob()
+                    .sample_weighted(1, seed, |&x| if x == 0 { 99.0 } else { 1.0 });
+            pick == vec![0]
+        })
+        .count();
+    assert!(picked_heavy > 350 && picked_heavy < 650, "{}", picked_heavy);
+}
+
+#[test]
+fn sample_weighted_skips_non_positive_weights() {
+    let mut result = (0..10)
+        .lob()
+        .sample_weighted(10, 1, |&x| if x % 2 == 0 { 1.0 } else { 0.0 });
+    result.sort_unstable();
+    assert_eq!(result, vec![0, 2, 4, 6, 8]);
+}
+
+#[test]
+fn sample_weighted_is_deterministic() {
+    let a = (0..500).lob().sample_weighted(4, 11, |&x| f64::from(x));
+    let b = (0..500).lob().sample_weighted(4, 11, |&x| f64::from(x));
+    assert_eq!(a, b);
+    assert_eq!(a.len(), 4);
+}
+
 #[test]
 fn sum_integers() {
     let sum = (1..=5).lob().sum::<i32>();
@@ -82,6 +166,54 @@ fn max_single() {
     assert_eq!(max, Some(42));
 }
 
+#[test]
+fn nth_largest_in_range() {
+    let data = vec![3, 1, 4, 1, 5, 9, 2, 6];
+    assert_eq!(data.clone().into_iter().lob().nth_largest(1), Some(9));
+    assert_eq!(data.clone().into_iter().lob().nth_largest(3), Some(5));
+    assert_eq!(data.into_iter().lob().nth_largest(8), Some(1));
+}
+
+#[test]
+fn nth_largest_beyond_count() {
+    assert_eq!(vec![3, 1, 4].into_iter().lob().nth_largest(4), None);
+    assert_eq!(Vec::<i32>::new().into_iter().lob().nth_largest(1), None);
+}
+
+#[test]
+fn nth_largest_zero() {
+    assert_eq!(vec![3, 1, 4].into_iter().lob().nth_largest(0), None);
+}
+
+#[test]
+fn nth_largest_ties() {
+    let data = vec![5, 3, 5, 1];
+    assert_eq!(data.clone().into_iter().lob().nth_largest(1), Some(5));
+    assert_eq!(data.clone().into_iter().lob().nth_largest(2), Some(5));
+    assert_eq!(data.into_iter().lob().nth_largest(3), Some(3));
+}
+
+#[test]
+fn nth_smallest_in_range() {
+    let data = vec![3, 1, 4, 1, 5, 9, 2, 6];
+    assert_eq!(data.clone().into_iter().lob().nth_smallest(1), Some(1));
+    assert_eq!(data.clone().into_iter().lob().nth_smallest(4), Some(3));
+    assert_eq!(data.into_iter().lob().nth_smallest(8), Some(9));
+}
+
+#[test]
+fn nth_smallest_beyond_count() {
+    assert_eq!(vec![3, 1, 4].into_iter().lob().nth_smallest(4), None);
+    assert_eq!(vec![3, 1, 4].into_iter().lob().nth_smallest(0), None);
+}
+
+#[test]
+fn nth_smallest_ties() {
+    let data = vec!["b", "a", "a", "c"];
+    assert_eq!(data.clone().into_iter().lob().nth_smallest(2), Some("a"));
+    assert_eq!(data.into_iter().lob().nth_smallest(3), Some("b"));
+}
+
 #[test]
 fn first_basic() {
     let first = (1..10).lob().first();
@@ -167,6 +299,39 @@ fn to_list_empty() {
     assert!(list.is_empty());
 }
 
+#[test]
+fn collect_strings_from_numbers() {
+    let strings = vec![1.5, -2.0, 3.25].into_iter().lob().collect_strings();
+    assert_eq!(strings, vec!["1.5", "-2", "3.25"]);
+}
+
+#[test]
+fn collect_strings_from_str() {
+    let strings = "a b c".split(' ').lob().collect_strings();
+    assert_eq!(
+        strings,
+        vec!["a".to_string(), "b".to_string(), "c".to_string()]
+    );
+}
+
+#[test]
+fn collect_strings_empty() {
+    let strings = Vec::<i32>::new().into_iter().lob().collect_strings();
+    assert!(strings.is_empty());
+}
+
+#[test]
+fn collect_set_drops_duplicates() {
+    let set = vec![3, 1, 3, 2, 1].into_iter().lob().collect_set();
+    assert_eq!(set, [1, 2, 3].into_iter().collect());
+}
+
+#[test]
+fn collect_set_empty() {
+    let set = Vec::<i32>::new().into_iter().lob().collect_set();
+    assert!(set.is_empty());
+}
+
 #[test]
 fn any_true() {
     let result = (1..10).lob().any(|x| x > 5);
@@ -204,3 +369,414 @@ fn all_empty() {
     let result: bool = empty.into_iter().lob().all(|x| x > 0);
     assert!(result); // Vacuous truth
 }
+
+#[test]
+fn argmax_basic() {
+    let idx = vec![3, 9, 4, 1].into_iter().lob().argmax();
+    assert_eq!(idx, Some(1));
+}
+
+#[test]
+fn argmax_ties_first() {
+    let idx = vec![5, 2, 5].into_iter().lob().argmax();
+    assert_eq!(idx, Some(0));
+}
+
+#[test]
+fn argmax_floats() {
+    let idx = vec![0.5, 2.5, 1.5].into_iter().lob().argmax();
+    assert_eq!(idx, Some(1));
+}
+
+#[test]
+fn argmax_empty() {
+    let idx = Vec::<i32>::new().into_iter().lob().argmax();
+    assert_eq!(idx, None);
+}
+
+#[test]
+fn argmin_basic() {
+    let idx = vec![3, 9, 0, 1].into_iter().lob().argmin();
+    assert_eq!(idx, Some(2));
+}
+
+#[test]
+fn argmin_ties_first() {
+    let idx = vec![4, 1, 7, 1].into_iter().lob().argmin();
+    assert_eq!(idx, Some(1));
+}
+
+#[test]
+fn argmin_empty() {
+    let idx = Vec::<i32>::new().into_iter().lob().argmin();
+    assert_eq!(idx, None);
+}
+
+#[test]
+fn take_reporting_exact_fit() {
+    let (items, truncated) = (0..3).lob().take_reporting(3);
+    assert_eq!(items, vec![0, 1, 2]);
+    assert!(!truncated);
+}
+
+#[test]
+fn take_reporting_fewer_than_n() {
+    let (items, truncated) = (0..2).lob().take_reporting(5);
+    assert_eq!(items, vec![0, 1]);
+    assert!(!truncated);
+}
+
+#[test]
+fn take_reporting_more_than_n() {
+    let (items, truncated) = (0..10).lob().take_reporting(4);
+    assert_eq!(items, vec![0, 1, 2, 3]);
+    assert!(truncated);
+}
+
+#[test]
+fn take_reporting_pulls_one_past_n() {
+    let mut pulled = 0;
+    let (items, truncated) = (0..100).lob().tee(|_| pulled += 1).take_reporting(2);
+    assert_eq!(items, vec![0, 1]);
+    assert!(truncated);
+    assert_eq!(pulled, 3);
+}
+
+#[test]
+fn first_where_multiple_matches() {
+    let first = (1..10).lob().first_where(|x| x % 3 == 0);
+    assert_eq!(first, Some(3));
+}
+
+#[test]
+fn first_where_no_match() {
+    let first = (1..10).lob().first_where(|x| *x > 100);
+    assert_eq!(first, None);
+}
+
+#[test]
+fn last_where_no_match() {
+    let last = vec!["ok", "ok"]
+        .into_iter()
+        .lob()
+        .last_where(|l| l.starts_with("ERROR"));
+    assert_eq!(last, None);
+}
+
+#[test]
+fn last_where_one_match() {
+    let last = vec!["ok", "ERROR a", "ok"]
+        .into_iter()
+        .lob()
+        .last_where(|l| l.starts_with("ERROR"));
+    assert_eq!(last, Some("ERROR a"));
+}
+
+#[test]
+fn last_where_multiple_matches() {
+    let last = vec!["ERROR a", "ok", "ERROR b", "ERROR c", "ok"]
+        .into_iter()
+        .lob()
+        .last_where(|l| l.starts_with("ERROR"));
+    assert_eq!(last, Some("ERROR c"));
+}
+
+#[test]
+fn last_where_empty() {
+    let last = Vec::<i32>::new().into_iter().lob().last_where(|_| true);
+    assert_eq!(last, None);
+}
+
+#[test]
+fn take_last_basic() {
+    let tail = (0..10).lob().take_last(3);
+    assert_eq!(tail, vec![7, 8, 9]);
+}
+
+#[test]
+fn take_last_more_than_available() {
+    let tail = (0..3).lob().take_last(10);
+    assert_eq!(tail, vec![0, 1, 2]);
+}
+
+#[test]
+fn take_last_zero() {
+    let tail = (0..3).lob().take_last(0);
+    assert!(tail.is_empty());
+}
+
+#[test]
+fn take_last_empty() {
+    let tail = Vec::<i32>::new().into_iter().lob().take_last(2);
+    assert!(tail.is_empty());
+}
+
+#[test]
+fn percentile_p0_is_min() {
+    let p0 = vec![5, 1, 9, 3].into_iter().lob().percentile(0.0);
+    assert_eq!(p0, Some(1.0));
+}
+
+#[test]
+fn percentile_p50_is_median() {
+    let odd = vec![7, 1, 3].into_iter().lob().percentile(50.0);
+    assert_eq!(odd, Some(3.0));
+
+    let even = vec![4, 1, 3, 2].into_iter().lob().percentile(50.0);
+    assert_eq!(even, Some(2.5));
+}
+
+#[test]
+fn percentile_p100_is_max() {
+    let p100 = vec![5, 1, 9, 3].into_iter().lob().percentile(100.0);
+    assert_eq!(p100, Some(9.0));
+}
+
+#[test]
+fn percentile_interpolates() {
+    let p95 = (1..=101).map(f64::from).lob().percentile(95.0).unwrap();
+    assert!((p95 - 96.0).abs() < 1e-9);
+
+    let p25 = vec![10.0, 20.0].into_iter().lob().percentile(25.0).unwrap();
+    assert!((p25 - 12.5).abs() < 1e-9);
+}
+
+#[test]
+fn percentile_empty() {
+    let p = Vec::<f64>::new().into_iter().lob().percentile(50.0);
+    assert_eq!(p, None);
+}
+
+#[test]
+#[should_panic(expected = "percentile must be between 0 and 100")]
+fn percentile_out_of_range() {
+    let _ = vec![1.0].into_iter().lob().percentile(101.0);
+}
+
+#[test]
+fn try_map_all_ok() {
+    let result = vec!["1", "2", "3"]
+        .into_iter()
+        .lob()
+        .try_map(|x| x.parse::<i32>());
+    assert_eq!(result, Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn try_map_early_error() {
+    let mut visited = Vec::new();
+    let result = vec!["1", "oops", "3"].into_iter().lob().try_map(|x| {
+        visited.push(x);
+        x.parse::<i32>().map_err(|_| format!("bad value: {x}"))
+    });
+    assert_eq!(result, Err("bad value: oops".to_string()));
+    assert_eq!(visited, vec!["1", "oops"]);
+}
+
+#[test]
+fn try_map_empty() {
+    let result: Result<Vec<i32>, String> = Vec::<&str>::new()
+        .into_iter()
+        .lob()
+        .try_map(|x| x.parse::<i32>().map_err(|e| e.to_string()));
+    assert_eq!(result, Ok(vec![]));
+}
+
+#[test]
+fn try_collect_all_ok() {
+    let result: Result<Vec<i32>, _> = vec!["1", "2", "3"]
+        .into_iter()
+        .lob()
+        .map(str::parse::<i32>)
+        .try_collect();
+    assert_eq!(result, Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn try_collect_stops_at_first_error() {
+    let mut pulled = Vec::new();
+    let result: Result<Vec<i32>, String> = vec!["1", "oops", "3", "nope"]
+        .into_iter()
+        .lob()
+        .map(|x| {
+            pulled.push(x);
+            x.parse::<i32>().map_err(|_| format!("bad value: {x}"))
+        })
+        .try_collect();
+    assert_eq!(result, Err("bad value: oops".to_string()));
+    assert_eq!(pulled, vec!["1", "oops"]);
+}
+
+#[test]
+fn try_collect_into_other_collections() {
+    let result: Result<std::collections::HashSet<i32>, String> =
+        vec![Ok(2), Ok(1), Ok(2)].into_iter().lob().try_collect();
+    assert_eq!(result, Ok(std::collections::HashSet::from([1, 2])));
+
+    let empty: Result<Vec<i32>, String> = Vec::new().into_iter().lob().try_collect();
+    assert_eq!(empty, Ok(vec![]));
+}
+
+#[test]
+fn is_empty_on_empty_stream() {
+    assert!(Vec::<i32>::new().into_iter().lob().is_empty());
+    assert!((1..10).lob().filter(|x| *x > 100).is_empty());
+}
+
+#[test]
+fn is_empty_on_non_empty_stream() {
+    assert!(!vec![1].into_iter().lob().is_empty());
+    // Short-circuits, so an unbounded stream is fine
+    assert!(!(0..).lob().is_empty());
+}
+
+#[test]
+fn min_max_multiple_elements() {
+    let result = vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter().lob().min_max();
+    assert_eq!(result, Some((1, 9)));
+}
+
+#[test]
+fn min_max_single_element() {
+    let result = vec![42].into_iter().lob().min_max();
+    assert_eq!(result, Some((42, 42)));
+}
+
+#[test]
+fn min_max_empty() {
+    let result = Vec::<i32>::new().into_iter().lob().min_max();
+    assert_eq!(result, None);
+}
+
+#[test]
+fn min_max_matches_separate_min_and_max() {
+    let data = vec!["pear", "apple", "fig", "zucchini", "kiwi"];
+    let (min, max) = data.clone().into_iter().lob().min_max().unwrap();
+    assert_eq!(Some(min), data.clone().into_iter().lob().min());
+    assert_eq!(Some(max), data.into_iter().lob().max());
+}
+
+#[test]
+fn find_map_found() {
+    let result = vec!["a", "12", "x", "34"]
+        .into_iter()
+        .lob()
+        .find_map(|s| s.parse::<i32>().ok());
+    assert_eq!(result, Some(12));
+}
+
+#[test]
+fn find_map_not_found() {
+    let result = vec!["a", "b"]
+        .into_iter()
+        .lob()
+        .find_map(|s| s.parse::<i32>().ok());
+    assert_eq!(result, None);
+}
+
+#[test]
+fn find_map_empty() {
+    let result = Vec::<&str>::new()
+        .into_iter()
+        .lob()
+        .find_map(|s| s.parse::<i32>().ok());
+    assert_eq!(result, None);
+}
+
+#[test]
+fn positions_no_matches() {
+    let result = (0..5).lob().positions(|x| *x > 10);
+    assert!(result.is_empty());
+}
+
+#[test]
+fn positions_all_match() {
+    let result = (0..4).lob().positions(|_| true);
+    assert_eq!(result, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn positions_scattered() {
+    let result = vec![1, 4, 6, 7, 9, 10]
+        .into_iter()
+        .lob()
+        .positions(|x| x % 2 == 0);
+    assert_eq!(result, vec![1, 2, 5]);
+}
+
+#[test]
+fn histogram_uniform() {
+    let counts = (0..100).lob().histogram(4, 0.0, 100.0);
+    assert_eq!(counts, vec![25, 25, 25, 25]);
+}
+
+#[test]
+fn histogram_out_of_range_clamps_to_end_buckets() {
+    let counts = vec![-50.0, 0.0, 5.0, 10.0, 999.0]
+        .into_iter()
+        .lob()
+        .histogram(2, 0.0, 10.0);
+    // -50 and 0 in the first bucket; 5, the upper bound 10, and 999 in the last
+    assert_eq!(counts, vec![2, 3]);
+}
+
+#[test]
+fn histogram_single_bin() {
+    let counts = vec![1, 5, 9].into_iter().lob().histogram(1, 0.0, 10.0);
+    assert_eq!(counts, vec![3]);
+}
+
+#[test]
+fn histogram_empty() {
+    let counts = Vec::<f64>::new().into_iter().lob().histogram(3, 0.0, 1.0);
+    assert_eq!(counts, vec![0, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "at least one bin")]
+fn histogram_zero_bins_panics() {
+    let _ = (0..3).lob().histogram(0, 0.0, 1.0);
+}
+
+#[test]
+fn mode_clear_winner() {
+    let result = vec![3, 1, 3, 2, 3, 1].into_iter().lob().mode();
+    assert_eq!(result, Some(3));
+}
+
+#[test]
+fn mode_tie_prefers_first_seen() {
+    let result = vec!["y", "x", "x", "y", "z"].into_iter().lob().mode();
+    assert_eq!(result, Some("y"));
+}
+
+#[test]
+fn mode_empty() {
+    let result = Vec::<i32>::new().into_iter().lob().mode();
+    assert_eq!(result, None);
+}
+
+#[test]
+fn split_results_mixed() {
+    let (oks, errs) = vec!["1", "two", "3", "", "5"]
+        .into_iter()
+        .lob()
+        .map(|s| s.parse::<i32>().map_err(|_| s))
+        .split_results();
+    assert_eq!(oks, vec![1, 3, 5]);
+    assert_eq!(errs, vec!["two", ""]);
+}
+
+#[test]
+fn split_results_all_ok() {
+    let (oks, errs): (Vec<i32>, Vec<String>) = (1..4).lob().map(Ok).split_results();
+    assert_eq!(oks, vec![1, 2, 3]);
+    assert!(errs.is_empty());
+}
+
+#[test]
+fn split_results_empty() {
+    let (oks, errs): (Vec<i32>, Vec<String>) = Vec::new().into_iter().lob().split_results();
+    assert!(oks.is_empty());
+    assert!(errs.is_empty());
+}
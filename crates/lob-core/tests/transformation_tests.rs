@@ -1,6 +1,7 @@
 //! Comprehensive tests for transformation operations
 
 use lob_core::LobExt;
+use std::collections::HashMap;
 
 #[test]
 fn map_basic() {
@@ -88,6 +89,21 @@ fn zip_empty() {
     assert!(result.is_empty());
 }
 
+#[test]
+fn zip_with_adds_pairwise() {
+    let result: Vec<_> = (0..5).lob().zip_with(10..15, |a, b| a + b).collect();
+    assert_eq!(result, vec![10, 12, 14, 16, 18]);
+}
+
+#[test]
+fn zip_with_stops_at_shorter() {
+    let result: Vec<_> = (0..10)
+        .lob()
+        .zip_with(vec!["a", "b"], |n, s| format!("{}{}", s, n))
+        .collect();
+    assert_eq!(result, vec!["a0", "b1"]);
+}
+
 #[test]
 fn flatten_basic() {
     let result: Vec<_> = vec![vec![1, 2], vec![3, 4], vec![5]]
@@ -128,3 +144,527 @@ fn chained_transformations() {
         .collect();
     assert_eq!(result, vec![(0, 1), (1, 3), (2, 5), (3, 7), (4, 9)]);
 }
+
+#[test]
+fn merge_interleaved() {
+    let result: Vec<_> = vec![1, 3, 5]
+        .into_iter()
+        .lob()
+        .merge(vec![2, 4, 6])
+        .collect();
+    assert_eq!(result, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn merge_fully_ordered() {
+    let result: Vec<_> = vec![1, 2].into_iter().lob().merge(vec![3, 4]).collect();
+    assert_eq!(result, vec![1, 2, 3, 4]);
+
+    let result: Vec<_> = vec![3, 4].into_iter().lob().merge(vec![1, 2]).collect();
+    assert_eq!(result, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn merge_with_duplicates() {
+    let result: Vec<_> = vec![1, 2, 2].into_iter().lob().merge(vec![2, 3]).collect();
+    assert_eq!(result, vec![1, 2, 2, 2, 3]);
+}
+
+#[test]
+fn merge_empty_side() {
+    let result: Vec<_> = vec![1, 2].into_iter().lob().merge(vec![]).collect();
+    assert_eq!(result, vec![1, 2]);
+
+    let result: Vec<_> = Vec::new().into_iter().lob().merge(vec![1, 2]).collect();
+    assert_eq!(result, vec![1, 2]);
+}
+
+#[test]
+fn interleave_equal_lengths() {
+    let result: Vec<_> = vec![1, 3, 5]
+        .into_iter()
+        .lob()
+        .interleave(vec![2, 4, 6])
+        .collect();
+    assert_eq!(result, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn interleave_left_longer() {
+    let result: Vec<_> = vec![1, 3, 5, 6]
+        .into_iter()
+        .lob()
+        .interleave(vec![2, 4])
+        .collect();
+    assert_eq!(result, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn interleave_right_longer() {
+    let result: Vec<_> = vec![1]
+        .into_iter()
+        .lob()
+        .interleave(vec![2, 3, 4])
+        .collect();
+    assert_eq!(result, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn interleave_empty_side() {
+    let result: Vec<_> = vec![1, 2].into_iter().lob().interleave(vec![]).collect();
+    assert_eq!(result, vec![1, 2]);
+
+    let result: Vec<_> = Vec::new()
+        .into_iter()
+        .lob()
+        .interleave(vec![1, 2])
+        .collect();
+    assert_eq!(result, vec![1, 2]);
+}
+
+#[test]
+fn sorted_by_floats_ascending() {
+    let result: Vec<f64> = vec![3.5, -2.0, 10.25, 0.0]
+        .into_iter()
+        .lob()
+        .sorted_by(|a, b| a.partial_cmp(b).unwrap())
+        .collect();
+    assert_eq!(result, vec![-2.0, 0.0, 3.5, 10.25]);
+}
+
+#[test]
+fn sorted_by_floats_descending() {
+    let result: Vec<f64> = vec![3.5, -2.0, 10.25, 0.0]
+        .into_iter()
+        .lob()
+        .sorted_by(|a, b| b.partial_cmp(a).unwrap())
+        .collect();
+    assert_eq!(result, vec![10.25, 3.5, 0.0, -2.0]);
+}
+
+#[test]
+fn sorted_by_is_stable() {
+    let result: Vec<_> = vec![(2, "a"), (1, "b"), (2, "c"), (1, "d")]
+        .into_iter()
+        .lob()
+        .sorted_by(|x, y| x.0.cmp(&y.0))
+        .collect();
+    assert_eq!(result, vec![(1, "b"), (1, "d"), (2, "a"), (2, "c")]);
+}
+
+#[test]
+fn sorted_by_empty() {
+    let result: Vec<i32> = Vec::new().into_iter().lob().sorted_by(Ord::cmp).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn combinations_three_choose_two() {
+    let result: Vec<_> = vec![1, 2, 3].into_iter().lob().combinations(2).collect();
+    assert_eq!(result, vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+}
+
+#[test]
+fn combinations_k_zero_is_one_empty_combo() {
+    let result: Vec<_> = vec![1, 2, 3].into_iter().lob().combinations(0).collect();
+    assert_eq!(result, vec![Vec::<i32>::new()]);
+}
+
+#[test]
+fn combinations_k_greater_than_len_is_empty() {
+    let result: Vec<_> = vec![1, 2].into_iter().lob().combinations(3).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn combinations_count_and_position_order() {
+    let result: Vec<_> = vec!["d", "c", "b", "a", "e"]
+        .into_iter()
+        .lob()
+        .combinations(3)
+        .collect();
+    assert_eq!(result.len(), 10);
+    assert_eq!(result[0], vec!["d", "c", "b"]);
+    assert_eq!(result[9], vec!["b", "a", "e"]);
+}
+
+#[test]
+fn permutations_three_pick_two() {
+    let result: Vec<_> = vec!['a', 'b', 'c']
+        .into_iter()
+        .lob()
+        .permutations(2)
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            vec!['a', 'b'],
+            vec!['a', 'c'],
+            vec!['b', 'a'],
+            vec!['b', 'c'],
+            vec!['c', 'a'],
+            vec!['c', 'b'],
+        ]
+    );
+}
+
+#[test]
+fn permutations_full_length() {
+    let result: Vec<_> = vec![1, 2, 3].into_iter().lob().permutations(3).collect();
+    assert_eq!(result.len(), 6);
+    assert_eq!(result[0], vec![1, 2, 3]);
+    assert_eq!(result[5], vec![3, 2, 1]);
+}
+
+#[test]
+fn permutations_edge_cases() {
+    let zero: Vec<_> = vec![1, 2].into_iter().lob().permutations(0).collect();
+    assert_eq!(zero, vec![Vec::<i32>::new()]);
+
+    let too_long: Vec<_> = vec![1, 2].into_iter().lob().permutations(3).collect();
+    assert!(too_long.is_empty());
+}
+
+#[test]
+fn shuffle_same_seed_same_permutation() {
+    let a: Vec<_> = (0..50).lob().shuffle(9).collect();
+    let b: Vec<_> = (0..50).lob().shuffle(9).collect();
+    assert_eq!(a, b);
+    assert_ne!(a, (0..50).collect::<Vec<_>>());
+}
+
+#[test]
+fn shuffle_preserves_elements() {
+    let mut result: Vec<_> = vec!["a", "b", "b", "c"]
+        .into_iter()
+        .lob()
+        .shuffle(3)
+        .collect();
+    result.sort_unstable();
+    assert_eq!(result, vec!["a", "b", "b", "c"]);
+}
+
+#[test]
+fn shuffle_empty_and_single() {
+    let empty: Vec<i32> = Vec::new().into_iter().lob().shuffle(1).collect();
+    assert!(empty.is_empty());
+
+    let single: Vec<_> = vec![7].into_iter().lob().shuffle(1).collect();
+    assert_eq!(single, vec![7]);
+}
+
+#[test]
+fn rank_distinct_values() {
+    let result: Vec<_> = vec![5, 1, 3].into_iter().lob().rank().collect();
+    assert_eq!(result, vec![(3, 5), (1, 1), (2, 3)]);
+}
+
+#[test]
+fn rank_ties_share_lowest_rank() {
+    let result: Vec<_> = vec!["b", "a", "b", "c", "a"]
+        .into_iter()
+        .lob()
+        .rank()
+        .collect();
+    assert_eq!(
+        result,
+        vec![(3, "b"), (1, "a"), (3, "b"), (5, "c"), (1, "a")]
+    );
+}
+
+#[test]
+fn rank_empty() {
+    let result: Vec<(usize, i32)> = Vec::new().into_iter().lob().rank().collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn intersperse_multiple() {
+    let result: Vec<_> = vec![1, 2, 3].into_iter().lob().intersperse(0).collect();
+    assert_eq!(result, vec![1, 0, 2, 0, 3]);
+}
+
+#[test]
+fn intersperse_single() {
+    let result: Vec<_> = vec![1].into_iter().lob().intersperse(0).collect();
+    assert_eq!(result, vec![1]);
+}
+
+#[test]
+fn intersperse_empty() {
+    let result: Vec<i32> = vec![].into_iter().lob().intersperse(0).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn intersperse_strings() {
+    let result: String = vec!["x", "y"].into_iter().lob().intersperse("-").collect();
+    assert_eq!(result, "x-y");
+}
+
+#[test]
+fn filter_map_parse() {
+    let result: Vec<_> = vec!["1", "two", "3", ""]
+        .into_iter()
+        .lob()
+        .filter_map(|s| s.parse::<i32>().ok())
+        .collect();
+    assert_eq!(result, vec![1, 3]);
+}
+
+#[test]
+fn filter_map_all_none() {
+    let result: Vec<i32> = (0..5).lob().filter_map(|_| None).collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn enumerate_from_one() {
+    let result: Vec<_> = vec!["a", "b", "c"]
+        .into_iter()
+        .lob()
+        .enumerate_from(1)
+        .collect();
+    assert_eq!(result, vec![(1, "a"), (2, "b"), (3, "c")]);
+}
+
+#[test]
+fn enumerate_from_zero_matches_enumerate() {
+    let from_zero: Vec<_> = (10..13).lob().enumerate_from(0).collect();
+    let plain: Vec<_> = (10..13).lob().enumerate().collect();
+    assert_eq!(from_zero, plain);
+}
+
+#[test]
+fn accumulate_running_concatenation() {
+    let result: Vec<_> = vec!["a", "b", "c"]
+        .into_iter()
+        .lob()
+        .accumulate(String::new(), |acc, s| acc + s)
+        .collect();
+    assert_eq!(result, vec!["", "a", "ab", "abc"]);
+}
+
+#[test]
+fn accumulate_empty_yields_init() {
+    let result: Vec<_> = Vec::<i32>::new()
+        .into_iter()
+        .lob()
+        .accumulate(0, |a, x| a + x)
+        .collect();
+    assert_eq!(result, vec![0]);
+}
+
+#[test]
+fn accumulate_matches_cumulative_sum() {
+    let acc: Vec<_> = (1..=5).lob().accumulate(0, |a, x| a + x).skip(1).collect();
+    let cum: Vec<_> = (1..=5).lob().cumulative_sum().collect();
+    assert_eq!(acc, cum);
+}
+
+#[test]
+fn tee_collects_side_copy() {
+    let mut side = Vec::new();
+    let main: Vec<_> = vec!["a".to_string(), "b".to_string()]
+        .into_iter()
+        .lob()
+        .tee(|s| side.push(s))
+        .map(|s| s.to_uppercase())
+        .collect();
+    assert_eq!(main, vec!["A", "B"]);
+    assert_eq!(side, vec!["a", "b"]);
+}
+
+#[test]
+fn tee_running_count_only_sees_consumed_items() {
+    let mut count = 0;
+    let first_two: Vec<_> = (0..100).lob().tee(|_| count += 1).take(2).collect();
+    assert_eq!(first_two, vec![0, 1]);
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn inspect_count_reports_every_n() {
+    let mut log = Vec::new();
+    let items: Vec<_> = (1..=7).lob().inspect_count_to(3, &mut log).collect();
+
+    assert_eq!(items, vec![1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(
+        String::from_utf8(log).unwrap(),
+        "processed: 3\nprocessed: 6\n"
+    );
+}
+
+#[test]
+fn inspect_count_only_counts_consumed_items() {
+    let mut log = Vec::new();
+    let first: Vec<_> = (0..100)
+        .lob()
+        .inspect_count_to(1, &mut log)
+        .take(2)
+        .collect();
+
+    assert_eq!(first, vec![0, 1]);
+    assert_eq!(
+        String::from_utf8(log).unwrap(),
+        "processed: 1\nprocessed: 2\n"
+    );
+}
+
+#[test]
+#[should_panic(expected = "greater than 0")]
+fn inspect_count_zero_panics() {
+    let _ = (0..3).lob().inspect_count(0);
+}
+
+#[test]
+fn cycle_n_repeats() {
+    let result = vec![1, 2].into_iter().lob().cycle_n(3).to_list();
+    assert_eq!(result, vec![1, 2, 1, 2, 1, 2]);
+}
+
+#[test]
+fn cycle_n_zero_is_empty() {
+    let result = vec![1, 2].into_iter().lob().cycle_n(0).to_list();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn cycle_n_one_is_identity() {
+    let result = vec!["a", "b", "c"].into_iter().lob().cycle_n(1).to_list();
+    assert_eq!(result, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn cycle_n_empty_source() {
+    let result: Vec<i32> = Vec::new().into_iter().lob().cycle_n(5).to_list();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn map_while_stops_at_first_parse_failure() {
+    let result: Vec<_> = vec!["10", "20", "trailer", "30"]
+        .into_iter()
+        .lob()
+        .map_while(|s| s.parse::<i32>().ok())
+        .collect();
+    assert_eq!(result, vec![10, 20]);
+}
+
+#[test]
+fn map_while_all_some() {
+    let result: Vec<_> = (1..4).lob().map_while(|x| Some(x * 10)).collect();
+    assert_eq!(result, vec![10, 20, 30]);
+}
+
+#[test]
+fn transpose_rectangular() {
+    let result: Vec<_> = vec![vec![1, 2, 3], vec![4, 5, 6]]
+        .into_iter()
+        .lob()
+        .transpose()
+        .collect();
+    assert_eq!(result, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+}
+
+#[test]
+fn transpose_ragged_truncates_to_shortest_row() {
+    let result: Vec<_> = vec![vec!["a", "b", "c"], vec!["d"], vec!["e", "f"]]
+        .into_iter()
+        .lob()
+        .transpose()
+        .collect();
+    assert_eq!(result, vec![vec!["a", "d", "e"]]);
+}
+
+#[test]
+fn transpose_empty() {
+    let result: Vec<Vec<i32>> = Vec::<Vec<i32>>::new()
+        .into_iter()
+        .lob()
+        .transpose()
+        .collect();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn flat_map_expands_each_element() {
+    let result: Vec<_> = (1..4).lob().flat_map(|n| vec![n; n]).collect();
+    assert_eq!(result, vec![1, 2, 2, 3, 3, 3]);
+}
+
+fn rows(data: &[&[(&str, &str)]]) -> Vec<HashMap<String, String>> {
+    data.iter()
+        .map(|row| {
+            row.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn map_values_uppercases_every_value() {
+    let input = rows(&[
+        &[("name", "ada"), ("city", "london")],
+        &[("name", "bo"), ("city", "paris")],
+    ]);
+    let result: Vec<_> = input
+        .into_iter()
+        .lob()
+        .map_values(|v| v.to_uppercase())
+        .collect();
+    assert_eq!(
+        result,
+        rows(&[
+            &[("name", "ADA"), ("city", "LONDON")],
+            &[("name", "BO"), ("city", "PARIS")],
+        ])
+    );
+}
+
+#[test]
+fn map_keys_normalizes_headers() {
+    let input = rows(&[&[("First Name", "ada"), ("AGE", "36")]]);
+    let result: Vec<_> = input
+        .into_iter()
+        .lob()
+        .map_keys(|k| k.to_lowercase().replace(' ', "_"))
+        .collect();
+    assert_eq!(result, rows(&[&[("first_name", "ada"), ("age", "36")]]));
+}
+
+#[test]
+fn rename_key_across_rows() {
+    let input = rows(&[
+        &[("mail", "a@x.io"), ("name", "ada")],
+        &[("mail", "b@x.io"), ("name", "bo")],
+        &[("name", "cy")],
+    ]);
+    let result: Vec<_> = input
+        .into_iter()
+        .lob()
+        .rename_key("mail", "email")
+        .collect();
+    assert_eq!(
+        result,
+        rows(&[
+            &[("email", "a@x.io"), ("name", "ada")],
+            &[("email", "b@x.io"), ("name", "bo")],
+            &[("name", "cy")],
+        ])
+    );
+}
+
+#[test]
+fn rename_key_replaces_existing_column() {
+    let input = rows(&[&[("old", "1"), ("new", "2")]]);
+    let result: Vec<_> = input.into_iter().lob().rename_key("old", "new").collect();
+    assert_eq!(result, rows(&[&[("new", "1")]]));
+}
+
+#[test]
+fn flat_map_empty_results() {
+    let result: Vec<i32> = (0..5).lob().flat_map(|_| Vec::new()).collect();
+    assert!(result.is_empty());
+}
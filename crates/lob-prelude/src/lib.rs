@@ -7,9 +7,15 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+use encoding_rs::Encoding;
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use flate2::read::MultiGzDecoder;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 // Re-export core types and traits
 pub use lob_core::{HashSet, Lob, LobExt};
@@ -20,6 +26,9 @@ pub use serde_json;
 // Re-export tabled for table output
 pub use tabled;
 
+// Re-export rayon for --jobs parallel mapping
+pub use rayon;
+
 /// Creates a Lob iterator from stdin lines
 ///
 /// This function reads lines from stdin and returns a `Lob` iterator over them.
@@ -38,14 +47,46 @@ pub use tabled;
 #[must_use]
 pub fn input() -> Lob<impl Iterator<Item = String>> {
     let stdin = io::stdin();
-    Lob::new(
-        stdin
-            .lock()
-            .lines()
-            .map_while(Result::ok)
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty()),
-    )
+    Lob::new(trimmed_lines(stdin.lock(), false))
+}
+
+/// Like [`input`], but panics on the first line that cannot be read
+///
+/// `input` stops quietly at an unreadable line (for example invalid UTF-8);
+/// this variant reports the line number instead. Generated code uses it for
+/// `--strict`.
+#[must_use]
+pub fn input_strict() -> Lob<impl Iterator<Item = String>> {
+    let stdin = io::stdin();
+    Lob::new(trimmed_lines(stdin.lock(), true))
+}
+
+/// Read lines, trimming surrounding whitespace and dropping empty lines
+fn trimmed_lines<R: BufRead>(reader: R, strict: bool) -> impl Iterator<Item = String> {
+    read_lines(reader, strict)
+        .map(trim_in_place)
+        .filter(|s| !s.is_empty())
+}
+
+/// Read lines until the first read error, which panics in strict mode
+fn read_lines<R: BufRead>(reader: R, strict: bool) -> impl Iterator<Item = String> {
+    reader
+        .lines()
+        .enumerate()
+        .map_while(move |(i, line)| match line {
+            Ok(line) => Some(line),
+            Err(e) if strict => panic!("--strict: line {}: could not read input: {}", i + 1, e),
+            Err(_) => None,
+        })
+}
+
+/// Trim a `String` without allocating a new one
+fn trim_in_place(mut s: String) -> String {
+    let end = s.trim_end().len();
+    s.truncate(end);
+    let start = s.len() - s.trim_start().len();
+    s.drain(..start);
+    s
 }
 
 /// Creates a Lob iterator from any iterable
@@ -89,30 +130,122 @@ pub fn range(start: i64, end: i64) -> Lob<impl Iterator<Item = i64>> {
 
 // File input helpers
 
+/// Magic bytes at the start of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Open an input file, transparently decompressing gzip content
+///
+/// Detection uses the gzip magic bytes rather than the extension, so
+/// `access.log.gz` and a renamed archive both work.
+fn open_input(path: &std::path::Path) -> io::Result<Box<dyn BufRead>> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 2];
+    let is_gzip = file.read_exact(&mut magic).is_ok() && magic == GZIP_MAGIC;
+    file.seek(SeekFrom::Start(0))?;
+
+    if is_gzip {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Read each file with `read`, one after another
+///
+/// Files that cannot be opened are skipped, or panic in strict mode.
+fn read_files<T>(
+    paths: &[std::path::PathBuf],
+    strict: bool,
+    read: impl Fn(Box<dyn BufRead>) -> Vec<T>,
+) -> Vec<T> {
+    paths
+        .iter()
+        .flat_map(|path| match open_input(path) {
+            Ok(reader) => read(reader),
+            Err(e) if strict => panic!("--strict: could not open {}: {}", path.display(), e),
+            Err(_) => Vec::new(),
+        })
+        .collect()
+}
+
 /// Read lines from multiple files
 #[must_use]
-#[allow(clippy::needless_collect)]
 pub fn input_from_files(paths: &[std::path::PathBuf]) -> Lob<impl Iterator<Item = String>> {
-    let lines: Vec<String> = paths
-        .iter()
-        .flat_map(|path| {
-            File::open(path)
-                .ok()
-                .map(|file| {
-                    BufReader::new(file)
-                        .lines()
-                        .map_while(Result::ok)
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default()
-        })
-        .collect();
+    let lines = read_files(paths, false, |reader| {
+        trimmed_lines(reader, false).collect()
+    });
+    Lob::new(lines.into_iter())
+}
 
+/// Like [`input_from_files`], but panics on a missing file or unreadable line
+#[must_use]
+pub fn input_from_files_strict(paths: &[std::path::PathBuf]) -> Lob<impl Iterator<Item = String>> {
+    let lines = read_files(paths, true, |reader| trimmed_lines(reader, true).collect());
     Lob::new(lines.into_iter())
 }
 
+// Encoded input helpers
+
+/// Look up an encoding label such as `latin1` and return its canonical name
+///
+/// Labels follow the WHATWG Encoding Standard, so common aliases like
+/// `iso-8859-1` and `cp1252` are accepted.
+///
+/// # Errors
+///
+/// Returns a message if the label does not name a known encoding.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// assert_eq!(encoding_name("latin1"), Ok("windows-1252"));
+/// assert!(encoding_name("klingon").is_err());
+/// ```
+pub fn encoding_name(label: &str) -> Result<&'static str, String> {
+    lookup_encoding(label).map(Encoding::name)
+}
+
+fn lookup_encoding(label: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| format!("unknown encoding: {}", label))
+}
+
+/// Read lines in a legacy encoding such as Latin-1, converting them to UTF-8
+///
+/// Reads `paths` in order, or stdin when `paths` is empty. A byte-order mark,
+/// if present, takes precedence over `encoding`, and bytes that are invalid
+/// in the encoding become U+FFFD. Lines are trimmed and empty lines dropped,
+/// as with [`input`].
+///
+/// # Panics
+///
+/// Panics if `encoding` is not a known encoding label.
+#[must_use]
+pub fn input_encoded(
+    paths: &[std::path::PathBuf],
+    encoding: &str,
+) -> Lob<Box<dyn Iterator<Item = String>>> {
+    let encoding = lookup_encoding(encoding).unwrap_or_else(|e| panic!("{}", e));
+    let decode = move |reader: Box<dyn Read>| {
+        BufReader::new(
+            DecodeReaderBytesBuilder::new()
+                .encoding(Some(encoding))
+                .build(reader),
+        )
+    };
+
+    if paths.is_empty() {
+        let stdin = Box::new(io::stdin().lock());
+        return Lob::new(Box::new(trimmed_lines(decode(stdin), false)));
+    }
+    let lines = read_files(paths, false, |reader| {
+        trimmed_lines(decode(Box::new(reader)), false).collect()
+    });
+    Lob::new(Box::new(lines.into_iter()))
+}
+
 // CSV input helpers
 
 /// Parse CSV from stdin with headers
@@ -120,53 +253,156 @@ pub fn input_from_files(paths: &[std::path::PathBuf]) -> Lob<impl Iterator<Item
 pub fn input_csv() -> Lob<impl Iterator<Item = HashMap<String, String>>> {
     let stdin = io::stdin();
     let reader = BufReader::new(stdin.lock());
-    parse_csv_reader(reader)
+    parse_csv_reader(reader, false)
+}
+
+/// Like [`input_csv`], but panics on the first malformed record
+///
+/// `input_csv` skips records that fail to parse, such as rows with the wrong
+/// number of fields; this variant reports them. Generated code uses it for
+/// `--strict`.
+#[must_use]
+pub fn input_csv_strict() -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    let stdin = io::stdin();
+    let reader = BufReader::new(stdin.lock());
+    parse_csv_reader(reader, true)
 }
 
 /// Parse CSV from files with headers
 #[must_use]
-#[allow(clippy::needless_collect)]
 pub fn input_csv_from_files(
     paths: &[std::path::PathBuf],
 ) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
-    let rows: Vec<HashMap<String, String>> = paths
-        .iter()
-        .flat_map(|path| {
-            File::open(path)
-                .ok()
-                .map(|file| {
-                    let reader = BufReader::new(file);
-                    parse_csv_reader(reader).collect::<Vec<_>>()
-                })
-                .unwrap_or_default()
-        })
-        .collect();
-
+    let rows = read_files(paths, false, |reader| {
+        parse_csv_reader(reader, false).collect()
+    });
     Lob::new(rows.into_iter())
 }
 
-fn parse_csv_reader<R: io::Read>(reader: R) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
-    let mut csv_reader = csv::Reader::from_reader(reader);
+/// Like [`input_csv_from_files`], but panics on a missing file or malformed record
+#[must_use]
+pub fn input_csv_from_files_strict(
+    paths: &[std::path::PathBuf],
+) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    let rows = read_files(paths, true, |reader| {
+        parse_csv_reader(reader, true).collect()
+    });
+    Lob::new(rows.into_iter())
+}
 
-    let headers: Vec<String> = csv_reader
-        .headers()
-        .ok()
-        .map(|h| h.iter().map(|s| s.to_string()).collect())
-        .unwrap_or_default();
+fn parse_csv_reader<R: io::Read>(
+    reader: R,
+    strict: bool,
+) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    let csv_reader = csv::Reader::from_reader(reader);
+    let nulls = NULL_VALUES.get().map_or(&[][..], Vec::as_slice);
+    Lob::new(read_rows(csv_reader, nulls, strict).into_iter())
+}
 
-    let rows: Vec<HashMap<String, String>> = csv_reader
+/// Read every record as a header-keyed row, leaving out null cells
+///
+/// Malformed records are skipped, or panic in strict mode.
+fn read_rows<R: io::Read>(
+    mut csv_reader: csv::Reader<R>,
+    nulls: &[String],
+    strict: bool,
+) -> Vec<HashMap<String, String>> {
+    let headers: Vec<String> = match csv_reader.headers() {
+        Ok(h) => h.iter().map(|s| s.to_string()).collect(),
+        Err(e) if strict => panic!("--strict: could not read the header row: {}", e),
+        Err(_) => Vec::new(),
+    };
+    remember_columns(&headers);
+
+    csv_reader
         .records()
-        .filter_map(Result::ok)
+        .filter_map(|record| match record {
+            Ok(record) => Some(record),
+            Err(e) if strict => panic!("--strict: {}", e),
+            Err(_) => None,
+        })
         .map(|record| {
-            let mut row = HashMap::new();
-            for (header, value) in headers.iter().zip(record.iter()) {
-                row.insert(header.clone(), value.to_string());
-            }
-            row
+            headers
+                .iter()
+                .zip(record.iter())
+                .filter(|(_, value)| !nulls.iter().any(|null| null == value))
+                .map(|(header, value)| (header.clone(), value.to_string()))
+                .collect()
         })
-        .collect();
+        .collect()
+}
 
-    Lob::new(rows.into_iter())
+/// Cell values that mean "missing", set by `--null-value`
+static NULL_VALUES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Treat these CSV/TSV cell values as missing
+///
+/// Matching cells are left out of the row, so `row.get("col")` returns
+/// `None` instead of the sentinel string. Generated code calls this for
+/// `--null-value` before any input is read.
+pub fn set_null_values(values: &[&str]) {
+    let _ = NULL_VALUES.set(values.iter().map(|v| v.to_string()).collect());
+}
+
+/// Convert string rows into rows of JSON values, typing each cell
+///
+/// Generated code applies this to CSV input for `--typed`. See
+/// [`typed_value`] for how each cell is converted.
+pub fn typed_rows<I>(rows: I) -> Lob<impl Iterator<Item = HashMap<String, serde_json::Value>>>
+where
+    I: IntoIterator<Item = HashMap<String, String>>,
+{
+    Lob::new(rows.into_iter().map(|row| {
+        row.into_iter()
+            .map(|(column, cell)| {
+                let value = typed_value(&cell);
+                (column, value)
+            })
+            .collect()
+    }))
+}
+
+/// Convert a cell to a JSON number or boolean when it looks like one
+///
+/// Integers and finite decimals become numbers and `true`/`false` become
+/// booleans; everything else stays a string. Integers with a leading zero,
+/// such as zip codes like `02134`, are kept as strings so they survive
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+/// use lob_prelude::serde_json::json;
+///
+/// assert_eq!(typed_value("42"), json!(42));
+/// assert_eq!(typed_value("-1.5"), json!(-1.5));
+/// assert_eq!(typed_value("true"), json!(true));
+/// assert_eq!(typed_value("02134"), json!("02134"));
+/// ```
+#[must_use]
+pub fn typed_value(cell: &str) -> serde_json::Value {
+    use serde_json::Value;
+
+    match cell {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    let digits = cell.trim_start_matches(['-', '+']);
+    if digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.") {
+        return Value::String(cell.to_string());
+    }
+    if let Ok(n) = cell.parse::<i64>() {
+        return Value::from(n);
+    }
+    if let Ok(n) = cell.parse::<u64>() {
+        return Value::from(n);
+    }
+    cell.parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map_or_else(|| Value::String(cell.to_string()), Value::Number)
 }
 
 // TSV input helpers
@@ -176,100 +412,827 @@ fn parse_csv_reader<R: io::Read>(reader: R) -> Lob<impl Iterator<Item = HashMap<
 pub fn input_tsv() -> Lob<impl Iterator<Item = HashMap<String, String>>> {
     let stdin = io::stdin();
     let reader = BufReader::new(stdin.lock());
-    parse_tsv_reader(reader)
+    parse_tsv_reader(reader, false)
+}
+
+/// Like [`input_tsv`], but panics on the first malformed record
+#[must_use]
+pub fn input_tsv_strict() -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    let stdin = io::stdin();
+    let reader = BufReader::new(stdin.lock());
+    parse_tsv_reader(reader, true)
 }
 
 /// Parse TSV from files with headers
 #[must_use]
-#[allow(clippy::needless_collect)]
 pub fn input_tsv_from_files(
     paths: &[std::path::PathBuf],
 ) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
-    let rows: Vec<HashMap<String, String>> = paths
-        .iter()
-        .flat_map(|path| {
-            File::open(path)
-                .ok()
-                .map(|file| {
-                    let reader = BufReader::new(file);
-                    parse_tsv_reader(reader).collect::<Vec<_>>()
-                })
-                .unwrap_or_default()
-        })
-        .collect();
+    let rows = read_files(paths, false, |reader| {
+        parse_tsv_reader(reader, false).collect()
+    });
+    Lob::new(rows.into_iter())
+}
 
+/// Like [`input_tsv_from_files`], but panics on a missing file or malformed record
+#[must_use]
+pub fn input_tsv_from_files_strict(
+    paths: &[std::path::PathBuf],
+) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    let rows = read_files(paths, true, |reader| {
+        parse_tsv_reader(reader, true).collect()
+    });
     Lob::new(rows.into_iter())
 }
 
-fn parse_tsv_reader<R: io::Read>(reader: R) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
-    let mut csv_reader = csv::ReaderBuilder::new()
+fn parse_tsv_reader<R: io::Read>(
+    reader: R,
+    strict: bool,
+) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    let csv_reader = csv::ReaderBuilder::new()
         .delimiter(b'\t')
         .from_reader(reader);
+    let nulls = NULL_VALUES.get().map_or(&[][..], Vec::as_slice);
+    Lob::new(read_rows(csv_reader, nulls, strict).into_iter())
+}
 
-    let headers: Vec<String> = csv_reader
-        .headers()
-        .ok()
-        .map(|h| h.iter().map(|s| s.to_string()).collect())
-        .unwrap_or_default();
+// JSON input helpers
 
-    let rows: Vec<HashMap<String, String>> = csv_reader
-        .records()
-        .filter_map(Result::ok)
-        .map(|record| {
-            let mut row = HashMap::new();
-            for (header, value) in headers.iter().zip(record.iter()) {
-                row.insert(header.clone(), value.to_string());
+/// Parse JSON from stdin
+///
+/// Newline-delimited JSON is streamed one line at a time. If the input starts
+/// with `[` or its first line is not a complete JSON value (pretty-printed
+/// input), the whole input is parsed instead: a single array yields its
+/// elements, and any other values are yielded one by one.
+#[must_use]
+pub fn input_json() -> Lob<impl Iterator<Item = serde_json::Value>> {
+    parse_json_reader(io::stdin().lock(), false)
+}
+
+/// Like [`input_json`], but panics on the first value that fails to parse
+///
+/// `input_json` skips malformed lines; this variant reports the line number
+/// and its content. Generated code uses it for `--strict`.
+#[must_use]
+pub fn input_json_strict() -> Lob<impl Iterator<Item = serde_json::Value>> {
+    parse_json_reader(io::stdin().lock(), true)
+}
+
+/// Parse JSON from files (see [`input_json`] for the accepted layouts)
+#[must_use]
+pub fn input_json_from_files(
+    paths: &[std::path::PathBuf],
+) -> Lob<impl Iterator<Item = serde_json::Value>> {
+    let values = read_files(paths, false, |reader| {
+        parse_json_reader(reader, false).collect()
+    });
+    Lob::new(values.into_iter())
+}
+
+/// Like [`input_json_from_files`], but panics on a missing file or malformed value
+#[must_use]
+pub fn input_json_from_files_strict(
+    paths: &[std::path::PathBuf],
+) -> Lob<impl Iterator<Item = serde_json::Value>> {
+    let values = read_files(paths, true, |reader| {
+        parse_json_reader(reader, true).collect()
+    });
+    Lob::new(values.into_iter())
+}
+
+fn parse_json_reader<R: BufRead + 'static>(
+    mut reader: R,
+    strict: bool,
+) -> Lob<Box<dyn Iterator<Item = serde_json::Value>>> {
+    // Everything read so far, blank lines included, so error positions stay
+    // relative to the start of the input
+    let mut consumed = String::new();
+    let mut first_line = 0;
+    loop {
+        let start = consumed.len();
+        match reader.read_line(&mut consumed) {
+            Ok(0) => return Lob::new(Box::new(std::iter::empty())),
+            Ok(_) => first_line += 1,
+            Err(e) if strict => panic!("--strict: could not read input: {}", e),
+            Err(_) => return Lob::new(Box::new(std::iter::empty())),
+        }
+        if !consumed[start..].trim().is_empty() {
+            break;
+        }
+    }
+    let first = consumed.trim_start();
+
+    let first_value = if first.starts_with('[') {
+        None
+    } else {
+        serde_json::from_str(first).ok()
+    };
+
+    // Fast path: newline-delimited JSON
+    if let Some(value) = first_value {
+        let rest = read_lines(reader, strict)
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(move |(i, line)| match serde_json::from_str(&line) {
+                Ok(value) => Some(value),
+                Err(e) if strict => panic!(
+                    "--strict: line {}: invalid JSON ({}): {}",
+                    first_line + i + 1,
+                    e,
+                    line
+                ),
+                Err(_) => None,
+            });
+        return Lob::new(Box::new(std::iter::once(value).chain(rest)));
+    }
+
+    // Whole-document path: pretty-printed values or a single array
+    let mut bytes = consumed.into_bytes();
+    match reader.read_to_end(&mut bytes) {
+        Err(e) if strict => panic!("--strict: could not read input: {}", e),
+        _ => {}
+    }
+    let document = String::from_utf8_lossy(&bytes);
+    let mut values = Vec::new();
+    for value in serde_json::Deserializer::from_str(&document).into_iter() {
+        match value {
+            Ok(value) => values.push(value),
+            Err(e) if strict => panic!(
+                "--strict: line {}: invalid JSON ({}): {}",
+                e.line(),
+                e,
+                document
+                    .lines()
+                    .nth(e.line().saturating_sub(1))
+                    .unwrap_or("")
+            ),
+            Err(_) if values.is_empty() => {
+                // Not a document either, most likely JSON lines with a
+                // malformed first line: parse line by line, skipping bad lines
+                values = document
+                    .lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect();
+                return Lob::new(Box::new(values.into_iter()));
             }
-            row
+            Err(_) => break,
+        }
+    }
+    if let [serde_json::Value::Array(items)] = values.as_mut_slice() {
+        values = std::mem::take(items);
+    }
+    Lob::new(Box::new(values.into_iter()))
+}
+
+/// Look up a nested value by a dotted path such as `user.address.city`
+///
+/// Object keys are matched by name; numeric segments index into arrays
+/// (`items.0.id`). Returns `None` if any segment is missing.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// let value = serde_json::json!({"user": {"tags": ["a", "b"]}});
+///
+/// assert_eq!(json_path(&value, "user.tags.1"), Some(&serde_json::json!("b")));
+/// assert_eq!(json_path(&value, "user.name"), None);
+/// ```
+#[must_use]
+pub fn json_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(items) => {
+                segment.parse::<usize>().ok().and_then(|i| items.get(i))
+            }
+            _ => None,
         })
-        .collect();
+}
+
+/// Flatten nested objects and arrays into a single-level row with dotted keys
+///
+/// Array elements get their index as the key segment (`tags.0`). Leaf values
+/// are stringified: strings as-is, everything else as JSON text, and empty
+/// objects or arrays as `{}` / `[]`. A scalar at the top level is stored
+/// under the empty key. The result can be filtered like a CSV row.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// let value = serde_json::json!({"user": {"name": "ada", "tags": ["x"]}, "age": 36});
+/// let row = flatten_json(&value);
+///
+/// assert_eq!(row["user.name"], "ada");
+/// assert_eq!(row["user.tags.0"], "x");
+/// assert_eq!(row["age"], "36");
+/// ```
+#[must_use]
+pub fn flatten_json(value: &serde_json::Value) -> HashMap<String, String> {
+    let mut row = HashMap::new();
+    flatten_json_into(value, String::new(), &mut row);
+    row
+}
+
+fn flatten_json_into(value: &serde_json::Value, prefix: String, row: &mut HashMap<String, String>) {
+    let key = |segment: &str| {
+        if prefix.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}.{}", prefix, segment)
+        }
+    };
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                flatten_json_into(v, key(k), row);
+            }
+        }
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            for (i, v) in items.iter().enumerate() {
+                flatten_json_into(v, key(&i.to_string()), row);
+            }
+        }
+        serde_json::Value::String(s) => {
+            row.insert(prefix, s.clone());
+        }
+        other => {
+            row.insert(prefix, other.to_string());
+        }
+    }
+}
+
+// JSON array input helpers
+
+/// Parse a single JSON array from stdin, yielding each element
+///
+/// Unlike `input_json`, the whole input is parsed as one document, so the
+/// array may span multiple lines. Input that is not a JSON array yields nothing.
+#[must_use]
+pub fn input_json_array() -> Lob<impl Iterator<Item = serde_json::Value>> {
+    let stdin = io::stdin();
+    parse_json_array_reader(stdin.lock())
+}
+
+/// Parse a single JSON array from each file, yielding each element
+#[must_use]
+pub fn input_json_array_from_files(
+    paths: &[std::path::PathBuf],
+) -> Lob<impl Iterator<Item = serde_json::Value>> {
+    let values = read_files(paths, false, |reader| {
+        parse_json_array_reader(reader).collect()
+    });
+    Lob::new(values.into_iter())
+}
+
+fn parse_json_array_reader<R: io::Read>(reader: R) -> Lob<impl Iterator<Item = serde_json::Value>> {
+    let values = match serde_json::from_reader(reader) {
+        Ok(serde_json::Value::Array(items)) => items,
+        _ => Vec::new(),
+    };
+
+    Lob::new(values.into_iter())
+}
+
+// Fixed-width input helpers
+
+/// Parse a field spec like `1-5,10-20,30` into 1-based inclusive character ranges
+///
+/// # Errors
+///
+/// Returns a message describing the first malformed range.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// assert_eq!(parse_field_spec("1-5,7"), Ok(vec![(1, 5), (7, 7)]));
+/// assert!(parse_field_spec("5-1").is_err());
+/// ```
+pub fn parse_field_spec(spec: &str) -> Result<Vec<(usize, usize)>, String> {
+    spec.split(',')
+        .map(|range| {
+            let range = range.trim();
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let parse = |n: &str| n.trim().parse::<usize>().ok().filter(|n| *n >= 1);
+            match (parse(start), parse(end)) {
+                (Some(start), Some(end)) if start <= end => Ok((start, end)),
+                _ => Err(format!(
+                    "invalid field range {:?}: expected N or N-M with 1 <= N <= M",
+                    range
+                )),
+            }
+        })
+        .collect()
+}
 
+/// Slice lines from stdin into fixed-width fields
+///
+/// `spec` lists 1-based inclusive character ranges (see `parse_field_spec`).
+/// Each field is trimmed of surrounding padding; ranges past the end of a
+/// short line yield an empty string.
+///
+/// # Panics
+///
+/// Panics if `spec` is not a valid field spec.
+#[must_use]
+pub fn input_fields(spec: &str) -> Lob<impl Iterator<Item = Vec<String>>> {
+    let ranges = parse_field_spec(spec).unwrap_or_else(|e| panic!("{}", e));
+    let stdin = io::stdin();
+    Lob::new(
+        stdin
+            .lock()
+            .lines()
+            .map_while(Result::ok)
+            .map(move |line| slice_fields(&line, &ranges)),
+    )
+}
+
+/// Slice lines from files into fixed-width fields
+///
+/// # Panics
+///
+/// Panics if `spec` is not a valid field spec.
+#[must_use]
+pub fn input_fields_from_files(
+    paths: &[std::path::PathBuf],
+    spec: &str,
+) -> Lob<impl Iterator<Item = Vec<String>>> {
+    let ranges = parse_field_spec(spec).unwrap_or_else(|e| panic!("{}", e));
+    let rows = read_files(paths, false, |reader| {
+        read_lines(reader, false)
+            .map(|line| slice_fields(&line, &ranges))
+            .collect()
+    });
     Lob::new(rows.into_iter())
 }
 
-// JSON input helpers
+fn slice_fields(line: &str, ranges: &[(usize, usize)]) -> Vec<String> {
+    ranges
+        .iter()
+        .map(|&(start, end)| {
+            line.chars()
+                .skip(start - 1)
+                .take(end - start + 1)
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
 
-/// Parse JSON lines from stdin
+// Web server log input helpers
+
+/// Fields produced by `input_logs`, in output column order
+const LOG_FIELDS: [&str; 10] = [
+    "ip",
+    "user",
+    "time",
+    "method",
+    "path",
+    "protocol",
+    "status",
+    "bytes",
+    "referrer",
+    "user_agent",
+];
+
+/// Parse Apache/Nginx common or combined log lines from stdin
+///
+/// Each line becomes a `HashMap` with the keys `ip`, `user`, `time`, `method`,
+/// `path`, `protocol`, `status`, and `bytes`, plus `referrer` and `user_agent`
+/// for the combined format. Malformed lines are skipped.
 #[must_use]
-pub fn input_json() -> Lob<impl Iterator<Item = serde_json::Value>> {
+pub fn input_logs() -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    remember_log_columns();
     let stdin = io::stdin();
     Lob::new(
         stdin
             .lock()
             .lines()
             .map_while(Result::ok)
-            .filter_map(|line| serde_json::from_str(&line).ok()),
+            .filter_map(|line| parse_log_line(&line)),
     )
 }
 
-/// Parse JSON lines from files
-#[must_use]
-#[allow(clippy::needless_collect)]
-pub fn input_json_from_files(
-    paths: &[std::path::PathBuf],
-) -> Lob<impl Iterator<Item = serde_json::Value>> {
-    let values: Vec<serde_json::Value> = paths
-        .iter()
-        .flat_map(|path| {
-            File::open(path)
-                .ok()
-                .map(|file| {
-                    BufReader::new(file)
-                        .lines()
-                        .map_while(Result::ok)
-                        .filter_map(|line| serde_json::from_str(&line).ok())
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default()
-        })
-        .collect();
-
-    Lob::new(values.into_iter())
+/// Parse Apache/Nginx common or combined log lines from files
+#[must_use]
+pub fn input_logs_from_files(
+    paths: &[std::path::PathBuf],
+) -> Lob<impl Iterator<Item = HashMap<String, String>>> {
+    remember_log_columns();
+    let rows = read_files(paths, false, |reader| {
+        read_lines(reader, false)
+            .filter_map(|line| parse_log_line(&line))
+            .collect()
+    });
+    Lob::new(rows.into_iter())
+}
+
+fn remember_log_columns() {
+    let columns: Vec<String> = LOG_FIELDS.iter().map(|f| f.to_string()).collect();
+    remember_columns(&columns);
+}
+
+fn parse_log_line(line: &str) -> Option<HashMap<String, String>> {
+    static LOG_LINE: OnceLock<regex::Regex> = OnceLock::new();
+    let regex = LOG_LINE.get_or_init(|| {
+        regex::Regex::new(
+            r#"^(\S+) \S+ (\S+) \[([^\]]+)\] "(\S+) (\S+) (\S+)" (\d{3}) (\d+|-)(?: "([^"]*)" "([^"]*)")?"#,
+        )
+        .expect("log line regex is valid")
+    });
+
+    let caps = regex.captures(line.trim())?;
+    Some(
+        LOG_FIELDS
+            .iter()
+            .enumerate()
+            .filter_map(|(i, field)| {
+                caps.get(i + 1)
+                    .map(|m| (field.to_string(), m.as_str().to_string()))
+            })
+            .collect(),
+    )
+}
+
+// Text helpers
+
+/// Build a predicate that tests lines against a regular expression
+///
+/// The pattern is compiled once, so the predicate is cheap to call per line.
+///
+/// # Panics
+///
+/// Panics if `pattern` is not a valid regular expression.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// let lines = vec!["ERROR disk full".to_string(), "INFO ok".to_string()];
+/// let result: Vec<_> = lob(lines).filter(matches("ERROR|WARN")).collect();
+///
+/// assert_eq!(result, vec!["ERROR disk full"]);
+/// ```
+pub fn matches(pattern: &str) -> impl Fn(&String) -> bool {
+    let regex =
+        regex::Regex::new(pattern).unwrap_or_else(|e| panic!("invalid regex {:?}: {}", pattern, e));
+    move |line| regex.is_match(line)
+}
+
+/// Build a function that extracts the first capture group of a regular expression
+///
+/// Returns `None` when the pattern does not match. Accepts anything string-like,
+/// so it works with both `filter_map` (owned lines) and `map` over references.
+///
+/// # Panics
+///
+/// Panics if `pattern` is not a valid regular expression.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// let lines = vec!["GET id=42".to_string(), "noise".to_string()];
+/// let ids: Vec<_> = lob(lines).filter_map(capture(r"id=(\d+)")).collect();
+///
+/// assert_eq!(ids, vec!["42"]);
+/// ```
+pub fn capture<S: AsRef<str>>(pattern: &str) -> impl Fn(S) -> Option<String> {
+    let regex =
+        regex::Regex::new(pattern).unwrap_or_else(|e| panic!("invalid regex {:?}: {}", pattern, e));
+    move |line| {
+        regex
+            .captures(line.as_ref())
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str().to_string())
+    }
+}
+
+/// Build a function that splits a line on `sep`, for use with `flat_map`
+///
+/// Empty segments (from repeated, leading, or trailing separators) are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// let lines = vec!["a;b".to_string(), "c;".to_string()];
+/// let parts: Vec<_> = lob(lines).flat_map(split_lines_on(';')).collect();
+///
+/// assert_eq!(parts, vec!["a", "b", "c"]);
+/// ```
+pub fn split_lines_on(sep: char) -> impl Fn(String) -> Vec<String> {
+    move |line| {
+        line.split(sep)
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Parse a number, tolerating surrounding whitespace and thousands separators
+///
+/// # Examples
+///
+/// ```
+/// use lob_prelude::*;
+///
+/// assert_eq!(parse_num(" 1,234.5 "), Some(1234.5));
+/// assert_eq!(parse_num("n/a"), None);
+/// ```
+#[must_use]
+pub fn parse_num(s: &str) -> Option<f64> {
+    s.trim().replace(',', "").parse().ok()
+}
+
+// Progress helpers
+
+/// Report at least this often, measured in items
+const PROGRESS_EVERY_ITEMS: u64 = 100_000;
+
+/// Report at least this often, measured in time
+const PROGRESS_EVERY: Duration = Duration::from_secs(1);
+
+/// Iterator adapter returned by [`progress`]
+pub struct Progress<I, W> {
+    inner: I,
+    sink: W,
+    seen: u64,
+    start: Instant,
+    last_report: Instant,
+    finished: bool,
+}
+
+impl<I, W: io::Write> Progress<I, W> {
+    /// Number of items yielded so far
+    pub fn items_seen(&self) -> u64 {
+        self.seen
+    }
+
+    fn report(&mut self, last: bool) {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            self.seen as f64 / elapsed
+        } else {
+            0.0
+        };
+        let end = if last { "\n" } else { "" };
+        let _ = write!(self.sink, "\r{} items ({:.0}/s){}", self.seen, rate, end);
+        let _ = self.sink.flush();
+        self.last_report = Instant::now();
+    }
+}
+
+impl<I: Iterator, W: io::Write> Iterator for Progress<I, W> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(item) = self.inner.next() {
+            self.seen += 1;
+            if self.seen.is_multiple_of(PROGRESS_EVERY_ITEMS)
+                || self.last_report.elapsed() >= PROGRESS_EVERY
+            {
+                self.report(false);
+            }
+            Some(item)
+        } else {
+            if !self.finished {
+                self.finished = true;
+                self.report(true);
+            }
+            None
+        }
+    }
+}
+
+/// Count items as they pass and print a throughput line to stderr
+///
+/// The line is rewritten in place every 100,000 items or every second,
+/// whichever comes first, and finished with a newline once the input is
+/// exhausted. Stdout is left untouched.
+pub fn progress<I: IntoIterator>(iterable: I) -> Lob<Progress<I::IntoIter, io::Stderr>> {
+    lob(progress_to(iterable, io::stderr()))
+}
+
+fn progress_to<I: IntoIterator, W: io::Write>(iterable: I, sink: W) -> Progress<I::IntoIter, W> {
+    let now = Instant::now();
+    Progress {
+        inner: iterable.into_iter(),
+        sink,
+        seen: 0,
+        start: now,
+        last_report: now,
+        finished: false,
+    }
+}
+
+/// Print the `--summary` line to stderr: records emitted and time since `start`
+pub fn print_summary(records: usize, start: Instant) {
+    let noun = if records == 1 { "record" } else { "records" };
+    eprintln!(
+        "summary: {} {} in {:.3}s",
+        records,
+        noun,
+        start.elapsed().as_secs_f64()
+    );
+}
+
+// Panic recovery helpers
+
+/// Input records handed to the expression so far, for [`resilient`] warnings
+static RECORDS_READ: AtomicUsize = AtomicUsize::new(0);
+
+/// Count input records as they are read, so [`resilient`] can name the one that panicked
+pub fn count_records<I: IntoIterator>(iterable: I) -> Lob<impl Iterator<Item = I::Item>> {
+    lob(iterable.into_iter().inspect(|_| {
+        RECORDS_READ.fetch_add(1, Ordering::Relaxed);
+    }))
+}
+
+/// Iterator adapter returned by [`resilient`]
+pub struct Resilient<I> {
+    inner: I,
+    last_failed: Option<usize>,
+}
+
+impl<I: Iterator> Iterator for Resilient<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        loop {
+            let inner = &mut self.inner;
+            if let Ok(item) =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| inner.next()))
+            {
+                return item;
+            }
+            let record = RECORDS_READ.load(Ordering::Relaxed);
+            // Panicking again without reading anything new would loop forever
+            if self.last_failed == Some(record) {
+                eprintln!("Warning: stopping, input record {} panicked twice", record);
+                return None;
+            }
+            if record == 0 {
+                eprintln!("Warning: skipped an item after a panic");
+            } else {
+                eprintln!("Warning: skipped input record {} after a panic", record);
+            }
+            self.last_failed = Some(record);
+        }
+    }
+}
+
+/// Skip items whose processing panics instead of aborting the run
+///
+/// Each item is pulled under `catch_unwind`, so a panicking closure upstream
+/// (typically an `.unwrap()` in `map`) costs one item and a warning on
+/// stderr rather than the whole run. The panic message itself is still
+/// printed by the default panic hook. Generated code uses this for
+/// `--resilient`, together with [`count_records`] on the input.
+pub fn resilient<I: IntoIterator>(iterable: I) -> Lob<Resilient<I::IntoIter>> {
+    lob(Resilient {
+        inner: iterable.into_iter(),
+        last_failed: None,
+    })
+}
+
+// Random helpers
+
+/// Seed shared by the randomized helpers, fixed by `--seed`
+static SEED: OnceLock<u64> = OnceLock::new();
+
+/// Fix the seed returned by [`seed`]; generated code calls this for `--seed`
+pub fn set_seed(seed: u64) {
+    let _ = SEED.set(seed);
+}
+
+/// Seed for randomized operations
+///
+/// Returns the `--seed` value when one was given, otherwise a seed derived
+/// from the clock and process id, chosen once per run.
+pub fn seed() -> u64 {
+    *SEED.get_or_init(|| {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        #[allow(clippy::cast_possible_truncation)]
+        let nanos = nanos as u64;
+        nanos ^ u64::from(std::process::id()).rotate_left(32)
+    })
+}
+
+/// Randomized operations that draw their seed from [`seed`]
+pub trait SampleExt: IntoIterator + Sized {
+    /// Pick `n` elements uniformly at random (see [`Lob::sample_seeded`])
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lob_prelude::*;
+    ///
+    /// set_seed(42);
+    /// let picked = range(0, 100).sample(3);
+    ///
+    /// assert_eq!(picked, range(0, 100).sample_seeded(3, 42));
+    /// ```
+    fn sample(self, n: usize) -> Vec<Self::Item>;
+}
+
+impl<I: Iterator> SampleExt for Lob<I> {
+    fn sample(self, n: usize) -> Vec<I::Item> {
+        self.sample_seeded(n, seed())
+    }
+}
+
+// Column order helpers
+
+/// Header order of the CSV/TSV sources parsed so far
+///
+/// The first source sets the order; columns that only later sources have
+/// are appended after it.
+static SOURCE_COLUMNS: OnceLock<Vec<String>> = OnceLock::new();
+static EXTRA_SOURCE_COLUMNS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn remember_columns(headers: &[String]) {
+    let first = SOURCE_COLUMNS.get_or_init(|| headers.to_vec());
+    if first == headers {
+        return;
+    }
+    eprintln!(
+        "warning: input header ({}) differs from the first input's ({}); columns follow the first input's order",
+        headers.join(", "),
+        first.join(", ")
+    );
+    let mut extra = EXTRA_SOURCE_COLUMNS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    for header in headers {
+        if !first.contains(header) && !extra.contains(header) {
+            extra.push(header.clone());
+        }
+    }
+}
+
+fn source_columns() -> Option<Vec<String>> {
+    let first = SOURCE_COLUMNS.get()?;
+    let extra = EXTRA_SOURCE_COLUMNS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    Some(first.iter().chain(extra.iter()).cloned().collect())
+}
+
+/// Order the keys of a row for tabular output
+///
+/// Keys follow the column order of the parsed CSV/TSV input. Keys that were
+/// not in the source (or all keys, for JSON input) are sorted and placed last.
+pub fn ordered_keys<'a, I>(keys: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    order_keys(keys, source_columns().as_deref())
+}
+
+/// Order the keys of all rows for a tabular header
+///
+/// Rows can have different keys (null cells are left out, and input files
+/// may have different headers), so the header is the union of every row's
+/// keys, ordered like [`ordered_keys`].
+pub fn header_keys<'a, R, K>(rows: R) -> Vec<&'a str>
+where
+    R: IntoIterator<Item = K>,
+    K: IntoIterator<Item = &'a String>,
+{
+    let mut seen = HashSet::new();
+    ordered_keys(rows.into_iter().flatten().filter(|key| seen.insert(*key)))
+}
+
+fn order_keys<'a, I>(keys: I, columns: Option<&[String]>) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut keys: Vec<&str> = keys.into_iter().map(String::as_str).collect();
+    keys.sort_unstable();
+    if let Some(columns) = columns {
+        keys.sort_by_key(|k| columns.iter().position(|c| c == k).unwrap_or(usize::MAX));
+    }
+    keys
 }
 
 // CSV output helper
 
 /// Output data as CSV
+///
+/// Map-like items (such as parsed CSV rows) get a header row in source column
+/// order; other items are written with the `csv` crate's serializer.
 pub fn output_csv<T: serde::Serialize>(items: &[T]) {
     if items.is_empty() {
         return;
@@ -277,13 +1240,98 @@ pub fn output_csv<T: serde::Serialize>(items: &[T]) {
 
     let mut writer = csv::Writer::from_writer(io::stdout());
 
-    for item in items {
-        let _ = writer.serialize(item);
+    let rows: Option<Vec<serde_json::Map<String, serde_json::Value>>> = items
+        .iter()
+        .map(|item| match serde_json::to_value(item) {
+            Ok(serde_json::Value::Object(row)) => Some(row),
+            _ => None,
+        })
+        .collect();
+
+    if let Some(rows) = rows {
+        let headers = header_keys(rows.iter().map(serde_json::Map::keys));
+        let _ = writer.write_record(&headers);
+        for row in &rows {
+            let _ = writer.write_record(headers.iter().map(|h| match row.get(*h) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(value) => value.to_string(),
+            }));
+        }
+    } else {
+        for item in items {
+            let _ = writer.serialize(item);
+        }
     }
 
     let _ = writer.flush();
 }
 
+// JSON output helper
+
+/// Output items as a pretty-printed JSON array without collecting them first
+///
+/// Items are serialized as they are pulled from the iterator through a
+/// buffered writer, so memory use stays bounded for large exports.
+pub fn output_json_stream<I>(items: I)
+where
+    I: IntoIterator,
+    I::Item: serde::Serialize,
+{
+    let stdout = io::stdout();
+    if let Err(e) = write_json_stream(io::BufWriter::new(stdout.lock()), items) {
+        eprintln!("Error: failed to write JSON output: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn write_json_stream<W, I>(writer: W, items: I) -> io::Result<()>
+where
+    W: io::Write,
+    I: IntoIterator,
+    I::Item: serde::Serialize,
+{
+    use serde::Serializer as _;
+
+    let mut serializer = serde_json::Serializer::pretty(writer);
+    serializer.collect_seq(items)?;
+    let mut writer = serializer.into_inner();
+    writeln!(writer)?;
+    writer.flush()
+}
+
+// TOML output helper
+
+/// Output a value as a TOML document
+///
+/// The value must serialize to a table (a map or struct); anything else
+/// prints an error and exits with status 1.
+pub fn output_toml<T: serde::Serialize>(value: &T) {
+    match toml::to_string(value) {
+        Ok(doc) => print!("{}", doc),
+        Err(e) => {
+            eprintln!("Error: result cannot be written as TOML: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// YAML output helper
+
+/// Output a value as a YAML document
+///
+/// Iterator results are collected into a sequence before calling this, so
+/// each item becomes a `- ` entry.
+pub fn output_yaml<T: serde::Serialize>(value: &T) {
+    match serde_norway::to_string(value) {
+        Ok(doc) => print!("{}", doc),
+        Err(e) => {
+            eprintln!("Error: result cannot be written as YAML: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,13 +1358,225 @@ mod tests {
         assert_eq!(result, vec![4, 8]);
     }
 
+    #[test]
+    fn matches_alternation() {
+        let is_problem = matches("ERROR|WARN");
+        assert!(is_problem(&"ERROR disk full".to_string()));
+        assert!(is_problem(&"WARN low memory".to_string()));
+        assert!(!is_problem(&"INFO started".to_string()));
+    }
+
+    #[test]
+    fn capture_first_group() {
+        let extract_id = capture(r"id=(\d+)");
+        assert_eq!(extract_id("user id=42 ok"), Some("42".to_string()));
+        assert_eq!(extract_id("id=7 id=8"), Some("7".to_string()));
+        assert_eq!(extract_id("no match"), None);
+    }
+
+    #[test]
+    fn capture_without_group_is_none() {
+        let extract = capture("ERROR");
+        assert_eq!(extract("ERROR here".to_string()), None);
+    }
+
+    #[test]
+    fn parse_num_thousands_separator() {
+        assert_eq!(parse_num("1,234"), Some(1234.0));
+        assert_eq!(parse_num("1,234,567.89"), Some(1_234_567.89));
+    }
+
+    #[test]
+    fn parse_num_whitespace() {
+        assert_eq!(parse_num(" 42 "), Some(42.0));
+        assert_eq!(parse_num("\t-3.5\n"), Some(-3.5));
+    }
+
+    #[test]
+    fn parse_num_empty_and_invalid() {
+        assert_eq!(parse_num(""), None);
+        assert_eq!(parse_num("   "), None);
+        assert_eq!(parse_num("abc"), None);
+        assert_eq!(parse_num("12abc"), None);
+    }
+
+    #[test]
+    fn order_keys_follows_source_columns() {
+        let columns = vec!["name".to_string(), "age".to_string(), "city".to_string()];
+        let keys = [
+            "city".to_string(),
+            "extra".to_string(),
+            "age".to_string(),
+            "name".to_string(),
+        ];
+        assert_eq!(
+            order_keys(&keys, Some(&columns)),
+            vec!["name", "age", "city", "extra"]
+        );
+    }
+
+    #[test]
+    fn header_keys_is_union_of_rows() {
+        let rows = [
+            HashMap::from([("b".to_string(), 1)]),
+            HashMap::from([("a".to_string(), 2), ("b".to_string(), 3)]),
+        ];
+        assert_eq!(header_keys(rows.iter().map(HashMap::keys)), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn order_keys_sorted_without_source_columns() {
+        let keys = ["b".to_string(), "c".to_string(), "a".to_string()];
+        assert_eq!(order_keys(&keys, None), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn parse_combined_log_line() {
+        let line = r#"203.0.113.9 - frank [10/Oct/2000:13:55:36 -0700] "GET /index.html HTTP/1.1" 200 2326 "http://example.com/" "Mozilla/5.0""#;
+        let row = parse_log_line(line).unwrap();
+
+        assert_eq!(row["ip"], "203.0.113.9");
+        assert_eq!(row["user"], "frank");
+        assert_eq!(row["time"], "10/Oct/2000:13:55:36 -0700");
+        assert_eq!(row["method"], "GET");
+        assert_eq!(row["path"], "/index.html");
+        assert_eq!(row["protocol"], "HTTP/1.1");
+        assert_eq!(row["status"], "200");
+        assert_eq!(row["bytes"], "2326");
+        assert_eq!(row["referrer"], "http://example.com/");
+        assert_eq!(row["user_agent"], "Mozilla/5.0");
+    }
+
+    #[test]
+    fn parse_common_log_line() {
+        let line = r#"10.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "POST /api HTTP/1.0" 404 -"#;
+        let row = parse_log_line(line).unwrap();
+
+        assert_eq!(row["status"], "404");
+        assert_eq!(row["bytes"], "-");
+        assert!(!row.contains_key("user_agent"));
+    }
+
+    #[test]
+    fn parse_malformed_log_line() {
+        assert!(parse_log_line("not a log line").is_none());
+        assert!(parse_log_line("").is_none());
+    }
+
+    #[test]
+    fn parse_field_spec_ranges() {
+        assert_eq!(parse_field_spec("1-5,10-20"), Ok(vec![(1, 5), (10, 20)]));
+        assert_eq!(parse_field_spec(" 3 , 4-4"), Ok(vec![(3, 3), (4, 4)]));
+    }
+
+    #[test]
+    fn parse_field_spec_invalid() {
+        assert!(parse_field_spec("").is_err());
+        assert!(parse_field_spec("0-3").is_err());
+        assert!(parse_field_spec("4-2").is_err());
+        assert!(parse_field_spec("a-b").is_err());
+    }
+
+    #[test]
+    fn slice_fields_short_line() {
+        let ranges = [(1, 3), (5, 8), (20, 25)];
+        assert_eq!(slice_fields("abc defg", &ranges), vec!["abc", "defg", ""]);
+        assert_eq!(slice_fields("ab", &ranges), vec!["ab", "", ""]);
+    }
+
+    #[test]
+    fn split_lines_on_basic() {
+        let split = split_lines_on(',');
+        assert_eq!(split("a,b,c".to_string()), vec!["a", "b", "c"]);
+        assert_eq!(split("single".to_string()), vec!["single"]);
+    }
+
+    #[test]
+    fn split_lines_on_drops_empty_segments() {
+        let split = split_lines_on(';');
+        assert_eq!(split("a;;b".to_string()), vec!["a", "b"]);
+        assert_eq!(split(";a;b;".to_string()), vec!["a", "b"]);
+        assert!(split(String::new()).is_empty());
+        assert!(split(";;".to_string()).is_empty());
+    }
+
+    #[test]
+    fn trimmed_lines_matches_allocating_trim() {
+        use std::io::Cursor;
+        let data = "  padded  \n\tindented\nplain\n   \n\ntrailing \r\n \u{a0}nbsp\u{a0}\n";
+
+        let expected: Vec<String> = Cursor::new(data)
+            .lines()
+            .map_while(Result::ok)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let result: Vec<String> = trimmed_lines(Cursor::new(data), false).collect();
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            result,
+            vec!["padded", "indented", "plain", "trailing", "nbsp"]
+        );
+    }
+
+    #[test]
+    fn json_path_nested_objects_and_arrays() {
+        let value = serde_json::json!({
+            "user": {"address": {"city": "Oslo"}, "orders": [{"id": 7}]}
+        });
+        assert_eq!(
+            json_path(&value, "user.address.city"),
+            Some(&serde_json::json!("Oslo"))
+        );
+        assert_eq!(
+            json_path(&value, "user.orders.0.id"),
+            Some(&serde_json::json!(7))
+        );
+    }
+
+    #[test]
+    fn json_path_missing() {
+        let value = serde_json::json!({"user": {"name": "x", "orders": []}});
+        assert_eq!(json_path(&value, "user.address.city"), None);
+        assert_eq!(json_path(&value, "user.name.first"), None);
+        assert_eq!(json_path(&value, "user.orders.0"), None);
+        assert_eq!(json_path(&value, "user.orders.x"), None);
+    }
+
+    #[test]
+    fn write_json_stream_is_valid_json() {
+        let mut out = Vec::new();
+        write_json_stream(&mut out, (1..=3).map(|i| HashMap::from([("id", i)]))).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed, serde_json::json!([{"id": 1}, {"id": 2}, {"id": 3}]));
+    }
+
+    #[test]
+    fn write_json_stream_matches_to_string_pretty() {
+        let items = vec!["a", "b"];
+        let mut out = Vec::new();
+        write_json_stream(&mut out, items.iter()).unwrap();
+
+        let expected = serde_json::to_string_pretty(&items).unwrap() + "\n";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn write_json_stream_empty() {
+        let mut out = Vec::new();
+        write_json_stream(&mut out, Vec::<i32>::new()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+    }
+
     #[test]
     fn test_parse_csv_from_string() {
         use std::io::Cursor;
         let data = "name,age,city\nAlice,30,NYC\nBob,25,LA\n";
         let cursor = Cursor::new(data);
 
-        let result: Vec<_> = parse_csv_reader(cursor).collect();
+        let result: Vec<_> = parse_csv_reader(cursor, false).collect();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].get("name"), Some(&"Alice".to_string()));
@@ -330,7 +1590,7 @@ mod tests {
         let data = "name,age\n";
         let cursor = Cursor::new(data);
 
-        let result: Vec<_> = parse_csv_reader(cursor).collect();
+        let result: Vec<_> = parse_csv_reader(cursor, false).collect();
 
         assert_eq!(result.len(), 0);
     }
@@ -341,7 +1601,7 @@ mod tests {
         let data = "name\tage\tcity\nAlice\t30\tNYC\nBob\t25\tLA\n";
         let cursor = Cursor::new(data);
 
-        let result: Vec<_> = parse_tsv_reader(cursor).collect();
+        let result: Vec<_> = parse_tsv_reader(cursor, false).collect();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].get("name"), Some(&"Alice".to_string()));
@@ -370,6 +1630,45 @@ mod tests {
         let _ = fs::remove_file(&file2);
     }
 
+    #[test]
+    fn test_input_from_gzip_file() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::env;
+        use std::fs;
+        use std::io::Write;
+
+        let file = env::temp_dir().join("test_input_gzip.log.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"first\nsecond\nthird\n").unwrap();
+        fs::write(&file, encoder.finish().unwrap()).unwrap();
+
+        let result: Vec<_> = input_from_files(std::slice::from_ref(&file)).collect();
+        assert_eq!(result, vec!["first", "second", "third"]);
+
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn test_input_csv_from_gzip_file() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::env;
+        use std::fs;
+        use std::io::Write;
+
+        let file = env::temp_dir().join("test_input_gzip.csv.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"name,age\nAlice,30\n").unwrap();
+        fs::write(&file, encoder.finish().unwrap()).unwrap();
+
+        let result: Vec<_> = input_csv_from_files(std::slice::from_ref(&file)).collect();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].get("name"), Some(&"Alice".to_string()));
+
+        let _ = fs::remove_file(&file);
+    }
+
     #[test]
     fn test_input_csv_from_files() {
         use std::env;
@@ -424,4 +1723,291 @@ mod tests {
 
         let _ = fs::remove_file(&file);
     }
+
+    #[test]
+    fn test_parse_json_array_from_string() {
+        use std::io::Cursor;
+        let data = "[\n  {\"a\": 1},\n  {\"a\": 2}\n]";
+        let cursor = Cursor::new(data);
+
+        let result: Vec<_> = parse_json_array_reader(cursor).collect();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[1].get("a").and_then(serde_json::Value::as_i64),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_parse_json_array_not_an_array() {
+        use std::io::Cursor;
+        let cursor = Cursor::new("{\"a\": 1}");
+
+        let result: Vec<_> = parse_json_array_reader(cursor).collect();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_progress_counts_items() {
+        let mut iter = progress_to(0..250_000u32, Vec::new());
+
+        assert_eq!(iter.by_ref().take(150_000).count(), 150_000);
+        assert_eq!(iter.items_seen(), 150_000);
+        let report = String::from_utf8(iter.sink.clone()).unwrap();
+        assert!(report.contains("\r100000 items ("));
+        assert!(!report.ends_with('\n'));
+
+        assert_eq!(iter.by_ref().count(), 100_000);
+        assert_eq!(iter.items_seen(), 250_000);
+        let report = String::from_utf8(iter.sink.clone()).unwrap();
+        assert!(report.contains("\r200000 items ("));
+        assert!(report.ends_with("/s)\n"));
+        assert!(report.contains("\r250000 items ("));
+    }
+
+    #[test]
+    fn test_progress_passes_items_through() {
+        let items: Vec<_> = progress_to(vec!["a", "b"], io::sink()).collect();
+
+        assert_eq!(items, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_null_value_cells_are_absent() {
+        use std::io::Cursor;
+        let data = "name,age,city\nAlice,NA,\\N\nBob,25,NA\n";
+        let nulls = vec!["NA".to_string(), "\\N".to_string()];
+
+        let rows = read_rows(csv::Reader::from_reader(Cursor::new(data)), &nulls, false);
+
+        assert_eq!(rows[0].get("name").map(String::as_str), Some("Alice"));
+        assert_eq!(rows[0].get("age"), None);
+        assert_eq!(rows[0].get("city"), None);
+        assert_eq!(rows[1].get("age").map(String::as_str), Some("25"));
+        assert_eq!(rows[1].get("city"), None);
+    }
+
+    #[test]
+    fn test_typed_rows_coerce_numbers_and_booleans() {
+        use serde_json::json;
+        use std::io::Cursor;
+        let data = "name,age,score,active,zip,note\nAlice,30,91.5,true,02134,\nBob,-4,1e3,false,10001,n/a\n";
+
+        let rows: Vec<_> = typed_rows(read_rows(
+            csv::Reader::from_reader(Cursor::new(data)),
+            &[],
+            false,
+        ))
+        .collect();
+
+        assert_eq!(rows[0]["name"], json!("Alice"));
+        assert_eq!(rows[0]["age"], json!(30));
+        assert_eq!(rows[0]["score"], json!(91.5));
+        assert_eq!(rows[0]["active"], json!(true));
+        assert_eq!(rows[0]["zip"], json!("02134"));
+        assert_eq!(rows[0]["note"], json!(""));
+        assert_eq!(rows[1]["age"], json!(-4));
+        assert_eq!(rows[1]["score"], json!(1000.0));
+        assert_eq!(rows[1]["active"], json!(false));
+        assert_eq!(rows[1]["zip"], json!(10001));
+        assert_eq!(rows[1]["note"], json!("n/a"));
+    }
+
+    #[test]
+    fn test_typed_value_keeps_non_numbers_as_strings() {
+        use serde_json::json;
+
+        assert_eq!(typed_value("0"), json!(0));
+        assert_eq!(typed_value("0.25"), json!(0.25));
+        assert_eq!(typed_value("18446744073709551615"), json!(u64::MAX));
+        assert_eq!(typed_value("NaN"), json!("NaN"));
+        assert_eq!(typed_value("inf"), json!("inf"));
+        assert_eq!(typed_value("True"), json!("True"));
+        assert_eq!(typed_value(" 7"), json!(" 7"));
+    }
+
+    #[test]
+    fn test_empty_cells_kept_without_null_values() {
+        use std::io::Cursor;
+        let data = "name,age\nAlice,\n";
+
+        let rows = read_rows(csv::Reader::from_reader(Cursor::new(data)), &[], false);
+
+        assert_eq!(rows[0].get("age").map(String::as_str), Some(""));
+    }
+
+    #[test]
+    fn test_flatten_json_nested_objects() {
+        let value = serde_json::json!({"user": {"name": "ada", "address": {"city": "London"}}});
+        let row = flatten_json(&value);
+
+        assert_eq!(row.len(), 2);
+        assert_eq!(row["user.name"], "ada");
+        assert_eq!(row["user.address.city"], "London");
+    }
+
+    #[test]
+    fn test_flatten_json_arrays() {
+        let value = serde_json::json!({"tags": ["a", "b"], "orders": [{"id": 7}], "none": []});
+        let row = flatten_json(&value);
+
+        assert_eq!(row["tags.0"], "a");
+        assert_eq!(row["tags.1"], "b");
+        assert_eq!(row["orders.0.id"], "7");
+        assert_eq!(row["none"], "[]");
+    }
+
+    #[test]
+    fn test_flatten_json_scalars() {
+        let value = serde_json::json!({"n": 1.5, "ok": true, "missing": null, "s": "text"});
+        let row = flatten_json(&value);
+
+        assert_eq!(row["n"], "1.5");
+        assert_eq!(row["ok"], "true");
+        assert_eq!(row["missing"], "null");
+        assert_eq!(row["s"], "text");
+
+        let row = flatten_json(&serde_json::json!(42));
+        assert_eq!(row, HashMap::from([(String::new(), "42".to_string())]));
+    }
+
+    #[test]
+    fn test_parse_json_ndjson() {
+        use std::io::Cursor;
+        let data = "{\"a\": 1}\n\n{\"a\": 2}\nnot json\n{\"a\": 3}\n";
+
+        let result: Vec<_> = parse_json_reader(Cursor::new(data), false).collect();
+
+        assert_eq!(
+            result,
+            vec![
+                serde_json::json!({"a": 1}),
+                serde_json::json!({"a": 2}),
+                serde_json::json!({"a": 3})
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_ndjson_malformed_first_line() {
+        use std::io::Cursor;
+        let data = "{\"a\": oops}\n{\"a\": 1}\n\n{\"a\": 2}\n";
+
+        let result: Vec<_> = parse_json_reader(Cursor::new(data), false).collect();
+
+        assert_eq!(
+            result,
+            vec![serde_json::json!({"a": 1}), serde_json::json!({"a": 2})]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_pretty_object() {
+        use std::io::Cursor;
+        let data = "{\n  \"user\": {\n    \"name\": \"ada\"\n  }\n}\n";
+
+        let result: Vec<_> = parse_json_reader(Cursor::new(data), false).collect();
+
+        assert_eq!(result, vec![serde_json::json!({"user": {"name": "ada"}})]);
+    }
+
+    #[test]
+    fn test_parse_json_pretty_array() {
+        use std::io::Cursor;
+        let data = "\n[\n  {\"a\": 1},\n  {\"a\": 2}\n]\n";
+
+        let result: Vec<_> = parse_json_reader(Cursor::new(data), false).collect();
+
+        assert_eq!(
+            result,
+            vec![serde_json::json!({"a": 1}), serde_json::json!({"a": 2})]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_lines_of_arrays_stay_whole() {
+        use std::io::Cursor;
+        let data = "[1, 2]\n[3]\n";
+
+        let result: Vec<_> = parse_json_reader(Cursor::new(data), false).collect();
+
+        assert_eq!(
+            result,
+            vec![serde_json::json!([1, 2]), serde_json::json!([3])]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_strict_accepts_valid_input() {
+        use std::io::Cursor;
+        let data = "\n{\"a\": 1}\n\n{\"a\": 2}\n";
+
+        let result: Vec<_> = parse_json_reader(Cursor::new(data), true).collect();
+
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 4: invalid JSON")]
+    fn test_parse_json_strict_reports_line() {
+        use std::io::Cursor;
+        let data = "\n{\"a\": 1}\n{\"a\": 2}\n{bad\n";
+
+        let _ = parse_json_reader(Cursor::new(data), true).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "line 3: invalid JSON (trailing comma at line 3 column 1): }")]
+    fn test_parse_json_strict_document_reports_line() {
+        use std::io::Cursor;
+        let data = "{\n  \"a\": 1,\n}\n";
+
+        let _ = parse_json_reader(Cursor::new(data), true).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "line: 3")]
+    fn test_read_rows_strict_reports_malformed_record() {
+        use std::io::Cursor;
+        let data = "a,b\n1,2\n3\n";
+
+        let _ = read_rows(csv::Reader::from_reader(Cursor::new(data)), &[], true);
+    }
+
+    #[test]
+    fn test_read_rows_lenient_skips_malformed_record() {
+        use std::io::Cursor;
+        let data = "a,b\n1,2\n3\n4,5\n";
+
+        let rows = read_rows(csv::Reader::from_reader(Cursor::new(data)), &[], false);
+
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn test_input_encoded_latin1() {
+        use std::env;
+        use std::fs;
+
+        // "café" and "naïve" in Latin-1: é is 0xE9, ï is 0xEF
+        let file = env::temp_dir().join("test_input_encoded_latin1.txt");
+        fs::write(&file, b"caf\xe9\nna\xefve\n").unwrap();
+
+        let result: Vec<_> = input_encoded(std::slice::from_ref(&file), "latin1").collect();
+        assert_eq!(result, vec!["café", "naïve"]);
+
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn test_resilient_skips_panicking_items() {
+        let result: Vec<_> = resilient((1..=5).map(|x| {
+            assert!(x != 3, "three");
+            x * 10
+        }))
+        .collect();
+        assert_eq!(result, vec![10, 20, 40, 50]);
+    }
 }